@@ -0,0 +1,567 @@
+//! Tools for checking that [`Mirror`] implementations behave like actual mirrors.
+//!
+//! This is mainly intended for authors of custom mirror shapes: [`run_conformance`]
+//! probes a mirror with generated rays, and checks that the tangents it reports
+//! satisfy the properties expected from reflective surfaces.
+
+use super::*;
+use alloc::vec;
+use nalgebra::RealField;
+
+/// Parameters of a [`run_conformance`] run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConformanceConfig<S, const D: usize> {
+    /// Center of the ball in which the origins of probe rays are generated.
+    pub center: SVector<S, D>,
+    /// Radius of the ball in which the origins of probe rays are generated.
+    pub radius: S,
+    /// Number of probe rays generated. Rays not intersecting with the mirror are ignored.
+    pub num_rays: usize,
+    /// Number of reflections traced for the reversibility check. Default: `8`
+    pub bounces: usize,
+    /// See [`Ray::closest_intersection`] for more info on the role of this field.
+    pub eps: S,
+    /// Maximum deviation accepted by each check.
+    pub tolerance: S,
+    /// Seed used to generate probe rays, runs with the same configuration are deterministic.
+    pub seed: u64,
+}
+
+impl<S, const D: usize> ConformanceConfig<S, D> {
+    #[inline]
+    #[must_use]
+    pub fn new(center: impl Into<SVector<S, D>>, radius: S, eps: S, tolerance: S) -> Self {
+        Self {
+            center: center.into(),
+            radius,
+            num_rays: 1024,
+            bounces: 8,
+            eps,
+            tolerance,
+            seed: 0,
+        }
+    }
+}
+
+/// The outcome of one of the checks performed by [`run_conformance`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckResult<S, const D: usize> {
+    /// Number of probe rays this check was applicable to.
+    pub checked: usize,
+    /// Number of probe rays for which the deviation exceeded the tolerance.
+    pub failures: usize,
+    /// The largest deviation measured.
+    pub worst_deviation: S,
+    /// The probe ray for which the largest deviation was measured, or, if a check
+    /// failed without a measurable deviation, the first ray that made it fail.
+    pub offending_ray: Option<Ray<S, D>>,
+}
+
+impl<S: RealField, const D: usize> CheckResult<S, D> {
+    fn new() -> Self {
+        Self {
+            checked: 0,
+            failures: 0,
+            worst_deviation: S::zero(),
+            offending_ray: None,
+        }
+    }
+
+    fn record(&mut self, ray: &Ray<S, D>, deviation: S, tolerance: &S) {
+        self.checked += 1;
+
+        let passed = deviation.is_finite() && deviation <= *tolerance;
+
+        if !passed {
+            self.failures += 1;
+        }
+
+        if deviation > self.worst_deviation {
+            self.worst_deviation = deviation;
+            self.offending_ray = Some(ray.clone());
+        } else if !passed && self.offending_ray.is_none() {
+            self.offending_ray = Some(ray.clone());
+        }
+    }
+
+    fn record_failure(&mut self, ray: &Ray<S, D>) {
+        self.checked += 1;
+        self.failures += 1;
+
+        if self.offending_ray.is_none() {
+            self.offending_ray = Some(ray.clone());
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.failures == 0
+    }
+}
+
+/// The outcome of a [`run_conformance`] run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConformanceReport<S, const D: usize> {
+    /// Number of probe rays that intersected with the mirror.
+    pub hits: usize,
+    /// Querying the mirror twice with the same ray yields the same intersection.
+    pub determinism: CheckResult<S, D>,
+    /// Reflecting a unit vector w.r.t. a reported tangent yields a unit vector.
+    pub norm_preservation: CheckResult<S, D>,
+    /// Reflecting a vector twice w.r.t. a reported tangent yields the same vector.
+    pub involution: CheckResult<S, D>,
+    /// The angle of reflection equals the angle of incidence, i.e. the component of the
+    /// ray's direction normal to the tangent is negated, and the tangential one is kept.
+    pub specular_angles: CheckResult<S, D>,
+    /// Reversing a ray after a few reflections makes it retrace it's path back to
+    /// it's origin. See [`check_reciprocity`].
+    pub reversibility: CheckResult<S, D>,
+}
+
+impl<S: RealField, const D: usize> ConformanceReport<S, D> {
+    /// All checks, along with their names.
+    #[inline]
+    #[must_use]
+    pub fn checks(&self) -> [(&'static str, &CheckResult<S, D>); 5] {
+        [
+            ("determinism", &self.determinism),
+            ("norm preservation", &self.norm_preservation),
+            ("involution", &self.involution),
+            ("specular angles", &self.specular_angles),
+            ("reversibility", &self.reversibility),
+        ]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.checks().iter().all(|(_, check)| check.passed())
+    }
+}
+
+/// A `SplitMix64` generator, good enough to spread probe rays around.
+struct Probes(u64);
+
+impl Probes {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `(0, 1]`
+    fn next_unit(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal, using the Box-Muller transform.
+    fn next_gaussian(&mut self) -> f64 {
+        let r = (-2. * ComplexField::ln(self.next_unit())).sqrt();
+        let (sin, _) = (core::f64::consts::TAU * self.next_unit()).sin_cos();
+        r * sin
+    }
+
+    /// Uniform on the unit sphere. The density of a vector of independent standard normals
+    /// only depends on it's norm, so it's direction is uniform.
+    fn next_unit_vector<const D: usize>(&mut self) -> SVector<f64, D> {
+        loop {
+            let v = SVector::<f64, D>::from_fn(|_, _| self.next_gaussian());
+            if let Some(v) = Unit::try_new(v, 0.) {
+                return v.into_inner();
+            }
+        }
+    }
+
+    /// Uniform in the unit ball, the proportion of points of the ball within a radius `r`
+    /// of it's center being `r^D`.
+    fn next_in_ball<const D: usize>(&mut self) -> SVector<f64, D> {
+        let radius = self.next_unit().powf(1. / D as f64);
+        self.next_unit_vector() * radius
+    }
+
+    fn next_ray<S: RealField, const D: usize>(
+        &mut self,
+        config: &ConformanceConfig<S, D>,
+    ) -> Ray<S, D> {
+        let offset = self.next_in_ball::<D>().map(|c| S::from_subset(&c));
+        let dir = self.next_unit_vector::<D>().map(|c| S::from_subset(&c));
+        Ray::new(offset * config.radius.clone() + &config.center, dir)
+    }
+}
+
+/// Returns the unit normal to `plane` and the projection of `v` onto `plane`.
+///
+/// Returns `None` if the normal can't be determined (`v` belongs to `plane`).
+fn decompose<S: RealField, const D: usize>(
+    plane: &Hyperplane<S, D>,
+    v: &SVector<S, D>,
+) -> Option<(SVector<S, D>, SVector<S, D>)> {
+    match plane {
+        Hyperplane::Plane(p) => {
+            let tangential = p.project(v);
+            Unit::try_new(v - &tangential, S::zero()).map(|n| (n.into_inner(), tangential))
+        }
+        Hyperplane::Normal(n) => Unit::try_new(n.as_ref().clone(), S::zero()).map(|n| {
+            let n = n.into_inner();
+            let tangential = v - &n * v.dot(&n);
+            (n, tangential)
+        }),
+    }
+}
+
+/// Traces `ray` for at most `bounces` reflections, and returns the points of it's path,
+/// (starting with `ray.origin`), the direction of the ray before it's last
+/// reflection, and the ray after it.
+fn trace<S: RealField, const D: usize>(
+    mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
+    ray: Ray<S, D>,
+    bounces: usize,
+    eps: &S,
+) -> (Vec<SVector<S, D>>, Unit<SVector<S, D>>, Ray<S, D>) {
+    let mut points = vec![ray.origin.clone()];
+    let mut incoming = ray.dir.clone();
+
+    let mut path = RayPath {
+        ray,
+        eps: eps.clone(),
+        mirror,
+    };
+
+    for _ in 0..bounces {
+        let dir = path.ray.dir.clone();
+        let Some(Ray { origin, .. }) = path.next() else {
+            break;
+        };
+
+        points.push(origin);
+        incoming = dir;
+    }
+
+    (points, incoming, path.ray)
+}
+
+/// The outcome of [`check_reciprocity`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReciprocityReport<S> {
+    /// Number of reflections of the forward path.
+    pub reflections: usize,
+    /// The largest distance between a point of the backward path and the matching point
+    /// of the forward one, including the distance between the forward path's origin and
+    /// the line the backward path's last leg lies on.
+    pub max_deviation: S,
+    /// Whether the backward path missed one of the forward path's reflections, or it's last
+    /// leg heads away from the forward path's origin.
+    pub diverged: bool,
+    /// The index, in the forward path (`0` being it's origin), of the first point, in the
+    /// order the backward path reaches them, it strays further than the tolerance from,
+    /// or fails to reach.
+    pub first_failure: Option<usize>,
+}
+
+impl<S> ReciprocityReport<S> {
+    /// Whether the check is meaningful, i.e. the forward path reflects at least twice.
+    #[inline]
+    #[must_use]
+    pub const fn is_applicable(&self) -> bool {
+        self.reflections >= 2
+    }
+
+    /// Whether the check is applicable, and the backward path retraces the forward one.
+    #[inline]
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.is_applicable() && self.first_failure.is_none()
+    }
+}
+
+/// Checks that `mirror` satisfies optical reciprocity along `ray`'s path.
+///
+/// `ray` is traced forward for at most [`config.bounces`](ConformanceConfig::bounces)
+/// reflections, then reversed at the last point of it's path, and traced backward, for the
+/// same number of reflections. The points of the backward path are then compared with those
+/// of the forward one, and those further than
+/// [`config.tolerance`](ConformanceConfig::tolerance) apart are reported. The other fields
+/// of `config`, used to generate probe rays, are ignored.
+///
+/// The backward ray starts [`config.eps`](ConformanceConfig::eps) away from the last
+/// reflection point, so that, like every leg of the forward path, it's first leg ignores
+/// intersections closer than `eps` to that point, and not only those closer than `eps` to
+/// it's origin.
+///
+/// See [`Ray::closest_intersection`] for more info on the role of `eps`.
+#[must_use]
+pub fn check_reciprocity<S: RealField, const D: usize>(
+    mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
+    ray: &Ray<S, D>,
+    config: &ConformanceConfig<S, D>,
+) -> ReciprocityReport<S> {
+    let ConformanceConfig {
+        bounces,
+        eps,
+        tolerance,
+        ..
+    } = config;
+
+    let (forward, incoming, _) = trace(mirror, ray.clone(), *bounces, eps);
+
+    let mut report = ReciprocityReport {
+        reflections: forward.len() - 1,
+        max_deviation: S::zero(),
+        diverged: false,
+        first_failure: None,
+    };
+
+    if !report.is_applicable() {
+        return report;
+    }
+
+    let [origin, .., last] = forward.as_slice() else {
+        unreachable!("paths always contain their origin")
+    };
+
+    let record = |report: &mut ReciprocityReport<S>, index: usize, deviation: S| {
+        if report.first_failure.is_none() && !(deviation.is_finite() && deviation <= *tolerance) {
+            report.first_failure = Some(index);
+        }
+
+        if deviation > report.max_deviation {
+            report.max_deviation = deviation;
+        }
+    };
+
+    let reversed_dir = -incoming.into_inner();
+    let start = last + &reversed_dir * eps.clone();
+    let reversed = Ray::new_unchecked_dir(start, reversed_dir);
+
+    let (backward, _, outgoing) = trace(mirror, reversed, report.reflections - 1, eps);
+
+    // `backward[i]` should match `forward[reflections - i]`, for `i >= 1`
+    for (i, got) in backward.iter().enumerate().skip(1) {
+        let index = report.reflections - i;
+        record(&mut report, index, (&forward[index] - got).norm());
+    }
+
+    if backward.len() != report.reflections {
+        report.diverged = true;
+        report
+            .first_failure
+            .get_or_insert(report.reflections - backward.len());
+        return report;
+    }
+
+    // the last leg of the backward path must head back to the forward path's origin
+    let to_origin = origin - &outgoing.origin;
+    let along = to_origin.dot(outgoing.dir.as_ref());
+    if along.is_negative() {
+        report.diverged = true;
+        report.first_failure.get_or_insert(0);
+        return report;
+    }
+
+    let off_line = to_origin - outgoing.dir.into_inner() * along;
+    record(&mut report, 0, off_line.norm());
+
+    report
+}
+
+/// Probes `mirror` with rays generated according to `config`, and checks that it behaves
+/// like a mirror.
+///
+/// See the fields of [`ConformanceReport`] for the list of performed checks.
+#[must_use]
+pub fn run_conformance<S: RealField, const D: usize>(
+    mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
+    config: &ConformanceConfig<S, D>,
+) -> ConformanceReport<S, D> {
+    let mut report = ConformanceReport {
+        hits: 0,
+        determinism: CheckResult::new(),
+        norm_preservation: CheckResult::new(),
+        involution: CheckResult::new(),
+        specular_angles: CheckResult::new(),
+        reversibility: CheckResult::new(),
+    };
+
+    let mut probes = Probes(config.seed);
+    let tol = &config.tolerance;
+
+    for _ in 0..config.num_rays {
+        let ray = probes.next_ray(config);
+
+        let Some((dist, tangent)) = ray.closest_intersection(mirror, config.eps.clone()) else {
+            continue;
+        };
+
+        report.hits += 1;
+
+        match ray.closest_intersection(mirror, config.eps.clone()) {
+            Some((d, t)) if d == dist && t == tangent => {
+                report.determinism.record(&ray, S::zero(), tol);
+            }
+            // different tangents at the same distance are a failure too
+            Some((d, _)) if d == dist => report.determinism.record_failure(&ray),
+            Some((d, _)) => report
+                .determinism
+                .record(&ray, (d - dist.clone()).abs(), tol),
+            None => report.determinism.record_failure(&ray),
+        }
+
+        let dir = ray.dir.as_ref();
+        let reflected = tangent.reflect(dir);
+
+        report
+            .norm_preservation
+            .record(&ray, (reflected.norm() - S::one()).abs(), tol);
+
+        report
+            .involution
+            .record(&ray, (tangent.reflect(&reflected) - dir).norm(), tol);
+
+        if let Some((normal, tangential)) = decompose(&tangent, dir) {
+            let ideal = (&tangential + &tangential) - dir;
+            let normal_error = (reflected.dot(&normal) + dir.dot(&normal)).abs();
+            report
+                .specular_angles
+                .record(&ray, (&reflected - ideal).norm().max(normal_error), tol);
+        }
+
+        let reciprocity = check_reciprocity(mirror, &ray, config);
+
+        if reciprocity.diverged {
+            report.reversibility.record_failure(&ray);
+        } else if reciprocity.is_applicable() {
+            report
+                .reversibility
+                .record(&ray, reciprocity.max_deviation, tol);
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_directions_are_unit_vectors() {
+        let mut probes = Probes(1);
+
+        for _ in 0..10_000 {
+            let v = probes.next_unit_vector::<5>();
+            assert!((v.norm() - 1.).abs() < 1e-12, "{v:?}");
+        }
+    }
+
+    #[test]
+    fn probe_origins_are_uniform_in_the_ball() {
+        const N: usize = 100_000;
+
+        fn check<const D: usize>() {
+            let mut probes = Probes(D as u64);
+            let mut inner = 0;
+
+            for _ in 0..N {
+                let norm = probes.next_in_ball::<D>().norm();
+                assert!(norm <= 1., "{norm}");
+                inner += usize::from(norm <= 0.5);
+            }
+
+            // the ball of radius 0.5 contains a proportion of 0.5^D of the points
+            let expected = 0.5f64.powi(D as i32);
+            let proportion = inner as f64 / N as f64;
+            assert!(
+                (proportion - expected).abs() < 0.01,
+                "{D}: {proportion}, {expected}"
+            );
+        }
+
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<10>();
+    }
+
+    #[test]
+    fn probe_directions_are_balanced() {
+        let mut probes = Probes(2);
+        let sum = (0..100_000).fold(SVector::<f64, 3>::zeros(), |sum, _| {
+            sum + probes.next_unit_vector::<3>()
+        });
+
+        assert!((sum / 100_000.).norm() < 0.01, "{sum:?}");
+    }
+
+    /// The horizontal line at height `y`. It's normal is skewed towards the direction of the
+    /// incoming ray, by `skew`, which makes reflections non-reciprocal if `skew != 0`.
+    struct Line {
+        y: f64,
+        skew: f64,
+    }
+
+    impl Mirror<2> for Line {
+        type Scalar = f64;
+
+        fn add_tangents(&self, ctx: &mut SimulationCtx<f64, 2>) {
+            let ray = ctx.ray();
+            if ray.dir.y != 0. {
+                let dist = (self.y - ray.origin.y) / ray.dir.y;
+                let normal = SVector::from([0., 1.]) + ray.dir.as_ref() * self.skew;
+                ctx.add_tangent(dist, Hyperplane::Normal(Unit::new_normalize(normal)));
+            }
+        }
+    }
+
+    fn corridor(skew: f64) -> [Line; 2] {
+        [Line { y: 0., skew }, Line { y: 1., skew: 0. }]
+    }
+
+    fn reciprocity_config() -> ConformanceConfig<f64, 2> {
+        ConformanceConfig::new([0., 0.], 1., 1e-9, 1e-9)
+    }
+
+    #[test]
+    fn reciprocal_mirrors_pass() {
+        let ray = Ray::new([0., 0.5], [1., 0.7]);
+        let report = check_reciprocity(&corridor(0.), &ray, &reciprocity_config());
+
+        assert_eq!(report.reflections, 8);
+        assert!(report.passed(), "{report:?}");
+        assert!(!report.diverged);
+        assert!(report.max_deviation < 1e-12, "{report:?}");
+    }
+
+    #[test]
+    fn non_reciprocal_mirrors_fail() {
+        let ray = Ray::new([0., 0.5], [1., 0.7]);
+        let report = check_reciprocity(&corridor(0.02), &ray, &reciprocity_config());
+
+        assert_eq!(report.reflections, 8);
+        assert!(!report.passed(), "{report:?}");
+        assert!(report.max_deviation > 1e-3, "{report:?}");
+
+        // the backward path's first leg is the forward path's last one, reversed, so it
+        // reaches point 7 of the forward path, but strays from it after reflecting there
+        let first_failure = report.first_failure.unwrap();
+        assert!(first_failure < 7, "{report:?}");
+    }
+
+    #[test]
+    fn reciprocity_needs_two_reflections() {
+        let config = ConformanceConfig {
+            bounces: 1,
+            ..reciprocity_config()
+        };
+
+        let ray = Ray::new([0., 0.5], [1., 0.7]);
+        let report = check_reciprocity(&corridor(0.2), &ray, &config);
+
+        assert_eq!(report.reflections, 1);
+        assert!(!report.is_applicable());
+        assert!(!report.passed());
+        assert_eq!(report.first_failure, None);
+    }
+}
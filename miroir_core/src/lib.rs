@@ -11,7 +11,52 @@ use core::{
 
 pub use nalgebra;
 
-use nalgebra::{zero, ComplexField, SMatrix, SVector, SimdComplexField, Unit};
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "libm")))]
+pub mod conformance;
+pub mod palette;
+pub mod util;
+
+use nalgebra::{
+    convert, one, zero, ComplexField, RealField, SMatrix, SVector, SimdBool, SimdComplexField,
+    SimdPartialOrd, Unit,
+};
+
+/// Maximum accepted difference between `1` and the squared norm of
+/// vectors checked with [`debug_assert_unit`].
+pub const UNIT_NORM_TOLERANCE: f64 = 1e-4;
+
+/// Asserts, in debug builds only, that a vector about to be wrapped with
+/// [`Unit::new_unchecked`] has a norm of `1`, up to [`UNIT_NORM_TOLERANCE`].
+///
+/// The second argument is printed with it's [`Debug`] impl if the assertion fails, usually,
+/// the shape whose geometric properties garantee that the vector is normalized.
+///
+/// ```ignore
+/// let n = (p - self.center).unscale(self.radius);
+/// debug_assert_unit!(n, self);
+/// Unit::new_unchecked(n)
+/// ```
+#[macro_export]
+macro_rules! debug_assert_unit {
+    ($v:expr, $context:expr $(,)?) => {
+        if cfg!(debug_assertions) {
+            $crate::__assert_unit(&$v, &$context);
+        }
+    };
+}
+
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn __assert_unit<S: SimdComplexField, const D: usize>(v: &SVector<S, D>, context: &dyn Debug) {
+    let norm_sq = v.norm_squared();
+    let error = (norm_sq.clone() - one()).simd_abs();
+
+    assert!(
+        error.simd_le(convert(UNIT_NORM_TOLERANCE)).all(),
+        "expected a unit vector, got a squared norm of {norm_sq:?}, from: {context:?}",
+    );
+}
 
 /// A hyperplane, stored as a basis of `D-1` vectors
 ///
@@ -216,10 +261,56 @@ impl<S: SimdComplexField, const D: usize> Hyperplane<S, D> {
     /// there is no need to renormalize the vector. However, some
     /// precision is lost when performing this optimisation. So, this function
     /// must be used with caution.
+    ///
+    /// In debug builds, this panics if the reflected vector isn't (roughly) normalized.
     #[inline]
     #[must_use]
     pub fn reflect_unit_optimised(&self, v: &Unit<SVector<S, D>>) -> Unit<SVector<S, D>> {
-        Unit::new_unchecked(self.reflect(v.as_ref()))
+        let reflected = self.reflect(v.as_ref());
+        debug_assert_unit!(reflected, self);
+        Unit::new_unchecked(reflected)
+    }
+}
+
+impl<S: ComplexField, const D: usize> Hyperplane<S, D> {
+    /// Returns `1 / |cos θ|`, where `θ` is the angle between `dir` and the normal to `self`.
+    ///
+    /// This estimates how ill-conditioned the distance to an intersection between a ray
+    /// directed by `dir`, and a surface tangent to `self` is: `1` for perpendicular hits,
+    /// growing unboundedly as the ray becomes parallel to `self`.
+    #[inline]
+    #[must_use]
+    pub fn incidence_condition(&self, dir: &Unit<SVector<S, D>>) -> S::RealField {
+        let dir = dir.as_ref();
+
+        let cos = match self {
+            Self::Plane(plane) => (dir - plane.project(dir)).norm(),
+            Self::Normal(normal) => normal.dotc(dir).modulus(),
+        };
+
+        cos.recip()
+    }
+
+    /// Returns the unit normal to `self` on the same side as `v`.
+    ///
+    /// Returns `None` if `v` belongs to `self`.
+    #[inline]
+    #[must_use]
+    pub fn normal_towards(&self, v: &SVector<S, D>) -> Option<Unit<SVector<S, D>>> {
+        match self {
+            Self::Plane(plane) => Unit::try_new(v - plane.project(v), zero()),
+            Self::Normal(normal) => {
+                let dot = normal.dotc(v).real();
+
+                if dot == zero() {
+                    None
+                } else if dot < zero() {
+                    Some(-normal.clone())
+                } else {
+                    Some(normal.clone())
+                }
+            }
+        }
     }
 }
 
@@ -289,9 +380,54 @@ impl<S: ComplexField, const D: usize> Ray<S, D> {
         mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
         eps: S::RealField,
     ) -> Option<(S, Hyperplane<S, D>)> {
-        let mut ctx = SimulationCtx::new(self, eps);
+        self.closest_intersection_counting_behind(mirror, eps).0
+    }
+
+    /// Like [`Self::closest_intersection`], but also returns the number of intersections
+    /// that were discarded because they were behind the ray's origin (at negative distances).
+    ///
+    /// If no intersection is found, but some were discarded for this reason, `self` is likely
+    /// pointing away from `mirror`.
+    #[inline]
+    #[must_use]
+    pub fn closest_intersection_counting_behind(
+        &self,
+        mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
+        eps: S::RealField,
+    ) -> (Option<(S, Hyperplane<S, D>)>, usize) {
+        let (closest, discarded) = self.closest_candidate(mirror, eps.clone(), eps);
+        let closest = closest.map(|candidate| (candidate.dist, candidate.tangent));
+        (closest, discarded.behind)
+    }
+
+    /// Like [`Self::closest_intersection`], but also returns an estimate of how
+    /// ill-conditioned the computed distance is, as reported by the intersected
+    /// mirror (see [`SimulationCtx::add_tangent_with_condition`]).
+    #[inline]
+    #[must_use]
+    pub fn closest_intersection_with_condition(
+        &self,
+        mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
+        eps: S::RealField,
+    ) -> Option<(S, Hyperplane<S, D>, S::RealField)> {
+        self.closest_candidate(mirror, eps.clone(), eps)
+            .0
+            .map(|candidate| (candidate.dist, candidate.tangent, candidate.condition))
+    }
+
+    /// Returns the selected intersection, among those farther than `eps`, considering
+    /// those within `window` of the closest one coincident, and the number of intersections
+    /// discarded for being behind `self`, or at NaN distances.
+    #[inline]
+    fn closest_candidate(
+        &self,
+        mirror: &(impl Mirror<D, Scalar = S> + ?Sized),
+        eps: S::RealField,
+        window: S::RealField,
+    ) -> (Option<Candidate<S, D>>, Discarded) {
+        let mut ctx = SimulationCtx::new(self, eps, window);
         mirror.add_tangents(&mut ctx);
-        ctx.reset_closest()
+        (ctx.resolve(), ctx.discarded)
     }
 }
 
@@ -304,22 +440,25 @@ impl<S, const D: usize> Ray<S, D> {
             dir,
         }
     }
+}
 
+impl<S: SimdComplexField, const D: usize> Ray<S, D> {
+    /// Does not normalize `dir`, which must thus already be a unit vector
+    /// (checked with [`debug_assert_unit`]).
     #[inline]
     #[must_use]
-    /// Does not normalize `dir`
     pub fn new_unchecked_dir(
         origin: impl Into<SVector<S, D>>,
         dir: impl Into<SVector<S, D>>,
     ) -> Self {
+        let dir = dir.into();
+        debug_assert_unit!(dir, "Ray::new_unchecked_dir");
         Self {
             origin: origin.into(),
-            dir: Unit::new_unchecked(dir.into()),
+            dir: Unit::new_unchecked(dir),
         }
     }
-}
 
-impl<S: SimdComplexField, const D: usize> Ray<S, D> {
     /// Reflect [`self.dir`](Self::dir) w.r.t. `dir`, using [`Hyperplane::reflect_unit`].
     #[inline]
     pub fn reflect_dir(&mut self, dir: &Hyperplane<S, D>) {
@@ -347,36 +486,290 @@ impl<S: SimdComplexField, const D: usize> Ray<S, D> {
     }
 }
 
+impl<S: RealField> Ray<S, 2> {
+    /// Creates a ray starting at `origin`, whose direction
+    /// is [`SVector::from_angle(theta)`](Vector2Ext::from_angle).
+    ///
+    /// ```
+    /// # use miroir::Ray;
+    /// use core::f64::consts::FRAC_PI_2;
+    ///
+    /// let ray = Ray::from_angle([1., 1.], FRAC_PI_2);
+    /// // pointing upwards
+    /// assert!((ray.dir.y - 1.).abs() < 1e-15);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_angle(origin: impl Into<SVector<S, 2>>, theta: S) -> Self {
+        let dir = SVector::from_angle(theta.clone());
+        debug_assert_unit!(dir, theta);
+        Self::new_unit_dir(origin, Unit::new_unchecked(dir))
+    }
+
+    /// The [polar angle](Vector2Ext::polar_angle) of this ray's direction.
+    #[inline]
+    #[must_use]
+    pub fn angle(&self) -> S {
+        self.dir.polar_angle()
+    }
+}
+
+/// Convenience methods for manipulating 2D vectors using angles.
+///
+/// All angles are in radians, and measured counterclockwise (assuming the y axis
+/// points up) from the positive x axis, or from another vector.
+///
+/// Some of these methods are named to avoid clashing with `nalgebra`'s inherent
+/// (`perp`, `angle`) methods, which take precedence over trait methods.
+pub trait Vector2Ext<S> {
+    /// The unit vector `[cos(theta), sin(theta)]`.
+    #[must_use]
+    fn from_angle(theta: S) -> Self;
+
+    /// The angle of `self` relative to the positive x axis, in `(-π, π]`.
+    ///
+    /// ```
+    /// # use miroir::{Vector2Ext, nalgebra::Vector2};
+    /// use core::f64::consts::PI;
+    ///
+    /// assert_eq!(Vector2::new(-1., 0.).polar_angle(), PI);
+    /// // even with a negative zero
+    /// assert_eq!(Vector2::new(-1., -0.).polar_angle(), PI);
+    /// assert_eq!(Vector2::new(0., -2.).polar_angle(), -PI / 2.);
+    /// ```
+    #[must_use]
+    fn polar_angle(&self) -> S;
+
+    /// `self`, rotated by `theta`.
+    #[must_use]
+    fn rotated(&self, theta: S) -> Self;
+
+    /// `self`, rotated by `π/2`, i. e. `[-y, x]`.
+    ///
+    /// ```
+    /// # use miroir::{Vector2Ext, nalgebra::Vector2};
+    /// assert_eq!(Vector2::new(1., 0.).perpendicular(), Vector2::new(0., 1.));
+    /// ```
+    #[must_use]
+    fn perpendicular(&self) -> Self;
+
+    /// The signed angle from `self` to `other`, in `(-π, π]`.
+    ///
+    /// Positive if `other` is counterclockwise from `self`.
+    #[must_use]
+    fn angle_between(&self, other: &Self) -> S;
+}
+
+impl<S: RealField> Vector2Ext<S> for SVector<S, 2> {
+    #[inline]
+    fn from_angle(theta: S) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        Self::new(cos, sin)
+    }
+
+    #[inline]
+    fn polar_angle(&self) -> S {
+        // adding zero turns `-0.0` into `0.0`, for which `atan2` returns `π`, instead of `-π`
+        (self.y.clone() + S::zero()).atan2(self.x.clone())
+    }
+
+    #[inline]
+    fn rotated(&self, theta: S) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        let [x, y] = [self.x.clone(), self.y.clone()];
+        Self::new(
+            x.clone() * cos.clone() - y.clone() * sin.clone(),
+            x * sin + y * cos,
+        )
+    }
+
+    #[inline]
+    fn perpendicular(&self) -> Self {
+        Self::new(-self.y.clone(), self.x.clone())
+    }
+
+    #[inline]
+    fn angle_between(&self, other: &Self) -> S {
+        // see `Self::polar_angle`
+        (self.perp(other) + S::zero()).atan2(self.dot(other))
+    }
+}
+
+/// A tangent added to a [`SimulationCtx`]
+struct Candidate<S: ComplexField, const D: usize> {
+    dist: S,
+    tangent: Hyperplane<S, D>,
+    priority: i32,
+    condition: S::RealField,
+}
+
+impl<S: ComplexField, const D: usize> Candidate<S, D> {
+    #[inline]
+    fn real_dist(&self) -> S::RealField {
+        self.dist.clone().real()
+    }
+}
+
+/// Maximum number of different priorities [`SimulationCtx`] can tell apart among
+/// intersections within it's epsilon of the closest one.
+const PRIORITY_BANDS: usize = 8;
+
+/// The closest candidate of each priority that can still be selected, sorted by
+/// increasing distance, (and thus, strictly increasing priority).
+///
+/// A candidate can't be selected if another one is at most as far, with at least the same
+/// priority, or if it's farther than the window's width from the closest candidate.
+struct PriorityBands<S: ComplexField, const D: usize> {
+    bands: [Option<Candidate<S, D>>; PRIORITY_BANDS],
+}
+
+impl<S: ComplexField, const D: usize> PriorityBands<S, D> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            bands: core::array::from_fn(|_| None),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = &Candidate<S, D>> {
+        self.bands.iter().map_while(Option::as_ref)
+    }
+
+    /// Keeps the candidates for which `f` returns `true`, in the same order.
+    #[inline]
+    fn retain(&mut self, mut f: impl FnMut(&Candidate<S, D>) -> bool) {
+        let mut len = 0;
+        for i in 0..PRIORITY_BANDS {
+            if let Some(candidate) = self.bands[i].take() {
+                if f(&candidate) {
+                    self.bands[len] = Some(candidate);
+                    len += 1;
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, candidate: Candidate<S, D>, window: &S::RealField) {
+        let d = candidate.real_dist();
+        let priority = candidate.priority;
+
+        if self
+            .iter()
+            .any(|band| band.priority >= priority && band.real_dist() <= d)
+        {
+            return;
+        }
+
+        self.retain(|band| band.priority > priority || band.real_dist() < d);
+
+        let len = self.iter().count();
+        let index = self.iter().take_while(|band| band.real_dist() < d).count();
+
+        if index == PRIORITY_BANDS {
+            // out of bands, keep the closest ones, to not shift the window
+            return;
+        }
+
+        for i in (index + 1..=len.min(PRIORITY_BANDS - 1)).rev() {
+            self.bands[i] = self.bands[i - 1].take();
+        }
+
+        self.bands[index] = Some(candidate);
+
+        if let Some(closest) = self.bands[0].as_ref() {
+            let max = closest.real_dist() + window.clone();
+            self.retain(|band| band.real_dist() <= max);
+        }
+    }
+
+    /// Returns the selected candidate, the one with the highest priority, and clears `self`.
+    #[inline]
+    fn resolve(&mut self) -> Option<Candidate<S, D>> {
+        let selected = self.bands.iter_mut().rev().find_map(Option::take);
+        self.bands.iter_mut().for_each(|band| *band = None);
+        selected
+    }
+}
+
+/// Numbers of tangents a [`SimulationCtx`] discarded, for other reasons than being too close.
+#[derive(Default)]
+struct Discarded {
+    /// At negative distances, behind the ray.
+    behind: usize,
+    /// At NaN distances.
+    nan: usize,
+}
+
 pub struct SimulationCtx<'a, S: ComplexField, const D: usize> {
     ray: &'a Ray<S, D>,
-    closest: Option<(S, Hyperplane<S, D>)>,
+    candidates: PriorityBands<S, D>,
     // garanteed to be positive
     epsilon: S::RealField,
+    // width of the window intersections are considered coincident within,
+    // garanteed to be positive
+    window: S::RealField,
+    discarded: Discarded,
+    // priority of the tangents currently being added, see `Prioritized`
+    priority: i32,
 }
 
 impl<'a, S: ComplexField, const D: usize> SimulationCtx<'a, S, D> {
     #[inline]
     #[must_use]
-    fn new(ray: &'a Ray<S, D>, epsilon: S::RealField) -> Self {
+    fn new(ray: &'a Ray<S, D>, epsilon: S::RealField, window: S::RealField) -> Self {
         Self {
             ray,
             epsilon: epsilon.abs(),
-            closest: None,
+            window: window.abs(),
+            candidates: PriorityBands::new(),
+            discarded: Discarded::default(),
+            priority: 0,
         }
     }
 
-    /// Stores `dist`, and `tangent_direction` along with it,
-    /// if it's positive and smaller than the `dist` stored internally.
+    /// Stores `dist`, and `tangent_direction` along with it, if it's greater than the ctx's
+    /// epsilon.
+    ///
+    /// Once every tangent is added, the closest one is selected. However, if others were
+    /// added at distances within the ray's epsilon (see [`Ray::closest_intersection`]) of it, the one with the highest priority
+    /// among them is selected instead, (see [`Prioritized`]), the closest one if they have
+    /// the same priority. The result doesn't depend on the order tangents are added in.
+    #[inline]
     pub fn add_tangent(&mut self, dist: S, tangent_direction: Hyperplane<S, D>) {
+        self.add_tangent_with_condition(dist, tangent_direction, one());
+    }
+
+    /// Like [`Self::add_tangent`], but also reports an estimate of how ill-conditioned `dist`
+    /// is, i. e. how much it's relative error is amplified, compared to that of the inputs it
+    /// was computed from (`1` meaning not at all, the default when using `add_tangent`).
+    ///
+    /// For smooth surfaces, [`Hyperplane::incidence_condition`] is a good default.
+    pub fn add_tangent_with_condition(
+        &mut self,
+        dist: S,
+        tangent_direction: Hyperplane<S, D>,
+        condition: S::RealField,
+    ) {
         let d = dist.clone().real();
 
-        if d >= self.epsilon
-            && self
-                .closest
-                .as_ref()
-                .map_or(true, |(t, _)| t.clone().real() > d)
-        {
-            self.closest = Some((dist, tangent_direction));
+        if d >= self.epsilon {
+            let candidate = Candidate {
+                dist,
+                tangent: tangent_direction,
+                priority: self.priority,
+                condition,
+            };
+
+            self.candidates.insert(candidate, &self.window);
+        } else if d < zero() {
+            self.discarded.behind += 1;
+        } else if !d.is_finite() {
+            // `d` isn't closer than `self.epsilon`, it's NaN
+            self.discarded.nan += 1;
+
+            #[cfg(feature = "trace")]
+            log::trace!("discarded an intersection at a NaN distance");
         }
     }
 
@@ -387,8 +780,8 @@ impl<'a, S: ComplexField, const D: usize> SimulationCtx<'a, S, D> {
     }
 
     #[inline]
-    fn reset_closest(&mut self) -> Option<(S, Hyperplane<S, D>)> {
-        self.closest.take()
+    fn resolve(&mut self) -> Option<Candidate<S, D>> {
+        self.candidates.resolve()
     }
 }
 
@@ -500,7 +893,7 @@ impl<const D: usize, T: Mirror<D>> Mirror<D> for Vec<T> {
     }
 }
 
-impl<'a, const D: usize, T: Mirror<D> + ?Sized> Mirror<D> for &'a T {
+impl<const D: usize, T: Mirror<D> + ?Sized> Mirror<D> for &T {
     type Scalar = T::Scalar;
     #[inline]
     fn add_tangents(&self, ctx: &mut SimulationCtx<Self::Scalar, D>) {
@@ -508,7 +901,7 @@ impl<'a, const D: usize, T: Mirror<D> + ?Sized> Mirror<D> for &'a T {
     }
 }
 
-impl<'a, const D: usize, T: Mirror<D> + ?Sized> Mirror<D> for &'a mut T {
+impl<const D: usize, T: Mirror<D> + ?Sized> Mirror<D> for &mut T {
     type Scalar = T::Scalar;
     #[inline]
     fn add_tangents(&self, ctx: &mut SimulationCtx<Self::Scalar, D>) {
@@ -516,6 +909,59 @@ impl<'a, const D: usize, T: Mirror<D> + ?Sized> Mirror<D> for &'a mut T {
     }
 }
 
+/// A wrapper around a mirror, giving it a priority over other mirrors,
+/// when they are intersected at the same point.
+///
+/// When a ray intersects with multiple mirrors at distances within
+/// it's epsilon (see [`Ray::closest_intersection`]) of each other, it reflects off of the
+/// one with the highest priority, regardless of the order in which they are stored. This is
+/// useful when mirrors are intentionally coincident (e. g. a small mirror placed on top of
+/// a larger one).
+///
+/// Unwrapped mirrors have priority `0`. Nested `Prioritized` wrappers
+/// override the priority of the outer ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Prioritized<M>(pub M, pub i32);
+
+impl<const D: usize, M: Mirror<D>> Mirror<D> for Prioritized<M> {
+    type Scalar = M::Scalar;
+    #[inline]
+    fn add_tangents(&self, ctx: &mut SimulationCtx<Self::Scalar, D>) {
+        let outer = core::mem::replace(&mut ctx.priority, self.1);
+        self.0.add_tangents(ctx);
+        ctx.priority = outer;
+    }
+}
+
+/// How a [`PolicyRayPath`] prevents it's ray from intersecting again with the surface it has
+/// just been reflected by, at (or around) the point of reflection.
+///
+/// Excluding the last surface hit altogether isn't provided: mirrors don't identify the
+/// surfaces they report tangents for, and a ray reflected inside a concave mirror (e. g. a
+/// sphere) must be able to hit that same surface next, which such a policy would prevent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ExclusionPolicy {
+    /// Ignore intersections closer than [`RayPath::eps`] to the point of reflection.
+    ///
+    /// This works with every mirror, but rays reflected at grazing angles can travel less
+    /// than `eps` before legitimately hitting another surface (or the same one, e. g. near
+    /// a sphere's tangent chord), and skip that intersection, tunneling through it.
+    #[default]
+    EpsTravel,
+    /// After each reflection, move the ray's origin [`RayPath::eps`] away from the surface,
+    /// along it's normal, on the side the reflected ray travels to, then only ignore
+    /// intersections behind the ray.
+    ///
+    /// Before the first reflection, the ray's origin isn't on a known surface, so intersections
+    /// closer than `eps` are still ignored, like with [`EpsTravel`](Self::EpsTravel).
+    ///
+    /// Grazing rays don't tunnel, since intersections are no longer filtered by distance.
+    /// However, surfaces closer than `eps` to the point of reflection, on that side (e. g. in
+    /// tight corners), can still be skipped. Intersections within `eps` of each other are still
+    /// considered coincident, see [`Prioritized`].
+    NormalOffset,
+}
+
 #[derive(Debug, Clone)]
 pub struct RayPath<'a, const D: usize, M: Mirror<D> + ?Sized> {
     pub ray: Ray<M::Scalar, D>,
@@ -526,14 +972,44 @@ pub struct RayPath<'a, const D: usize, M: Mirror<D> + ?Sized> {
     pub mirror: &'a M,
 }
 
+impl<'a, const D: usize, M: Mirror<D> + ?Sized> RayPath<'a, D, M> {
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        ray: Ray<M::Scalar, D>,
+        mirror: &'a M,
+        eps: <M::Scalar as ComplexField>::RealField,
+    ) -> Self {
+        Self { ray, eps, mirror }
+    }
+
+    /// Returns an iterator over the path of the same ray, in the same mirror, but that uses
+    /// `policy` to prevent it from intersecting again with the surface it was just reflected
+    /// by, (a `RayPath` always uses [`ExclusionPolicy::EpsTravel`]).
+    #[inline]
+    #[must_use]
+    pub const fn with_policy(self, policy: ExclusionPolicy) -> PolicyRayPath<'a, D, M> {
+        PolicyRayPath {
+            path: self,
+            policy,
+            state: PathState::Start,
+            nan_discarded: 0,
+        }
+    }
+}
+
 impl<'a, const D: usize, M: Mirror<D> + ?Sized> Iterator for RayPath<'a, D, M> {
     type Item = Ray<M::Scalar, D>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         let ray = &mut self.ray;
+
         ray.closest_intersection(self.mirror, self.eps.clone())
             .map(|(dist, direction)| {
+                #[cfg(feature = "trace")]
+                log::trace!("reflected at {:?}, after {dist:?}", ray.at(dist.clone()));
+
                 ray.advance(dist);
                 ray.reflect_dir(&direction);
                 ray.clone()
@@ -541,6 +1017,124 @@ impl<'a, const D: usize, M: Mirror<D> + ?Sized> Iterator for RayPath<'a, D, M> {
     }
 }
 
+/// A [`RayPath`] with a configurable [`ExclusionPolicy`], see [`RayPath::with_policy`].
+#[derive(Debug, Clone)]
+pub struct PolicyRayPath<'a, const D: usize, M: Mirror<D> + ?Sized> {
+    path: RayPath<'a, D, M>,
+    policy: ExclusionPolicy,
+    state: PathState,
+    // number of intersections discarded for being at NaN distances
+    nan_discarded: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathState {
+    /// The ray wasn't reflected yet, it's origin can lie on a surface.
+    Start,
+    Reflected,
+    /// No intersections were found, the ray escaped.
+    Ended,
+}
+
+impl<'a, const D: usize, M: Mirror<D> + ?Sized> PolicyRayPath<'a, D, M> {
+    /// The ray, as it was after it's last reflection.
+    ///
+    /// With [`ExclusionPolicy::NormalOffset`], it's origin has already been moved
+    /// away from the surface it was reflected by.
+    #[inline]
+    #[must_use]
+    pub const fn ray(&self) -> &Ray<M::Scalar, D> {
+        &self.path.ray
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn policy(&self) -> ExclusionPolicy {
+        self.policy
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn inner(&self) -> &RayPath<'a, D, M> {
+        &self.path
+    }
+
+    /// Returns the number of intersections discarded so far for being at NaN distances,
+    /// usually, a sign that a mirror is degenerate, or that the ray is (nearly) tangent to it.
+    ///
+    /// With the `log` feature enabled, a warning is emitted once the path ends, if this is
+    /// non-zero.
+    #[inline]
+    #[must_use]
+    pub const fn nan_discarded(&self) -> usize {
+        self.nan_discarded
+    }
+}
+
+impl<'a, const D: usize, M: Mirror<D> + ?Sized> Iterator for PolicyRayPath<'a, D, M> {
+    type Item = Ray<M::Scalar, D>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let RayPath { ray, eps, mirror } = &mut self.path;
+
+        let (min_dist, offset) = match (self.policy, self.state) {
+            (_, PathState::Ended) => return None,
+            (ExclusionPolicy::EpsTravel, _) => (eps.clone(), false),
+            // the ray's origin can lie on a surface before it's first reflection, (intentionally,
+            // or not) with nothing to move it away from, so distances are filtered instead
+            (ExclusionPolicy::NormalOffset, PathState::Start) => (eps.clone(), true),
+            (ExclusionPolicy::NormalOffset, PathState::Reflected) => (zero(), true),
+        };
+
+        // with `NormalOffset`, intersections are no longer filtered by distance, but those
+        // within `eps` of each other are still considered coincident, for `Prioritized` to work
+        let (closest, discarded) = ray.closest_candidate(*mirror, min_dist, eps.clone());
+        self.nan_discarded += discarded.nan;
+
+        let Some(Candidate { dist, tangent, .. }) = closest else {
+            self.state = PathState::Ended;
+
+            #[cfg(feature = "log")]
+            if self.nan_discarded > 0 {
+                log::warn!(
+                    "discarded {} intersections at NaN distances along a ray's path",
+                    self.nan_discarded,
+                );
+            }
+
+            return None;
+        };
+
+        self.state = PathState::Reflected;
+
+        #[cfg(feature = "trace")]
+        log::trace!("reflected at {:?}, after {dist:?}", ray.at(dist.clone()));
+
+        ray.advance(dist);
+        ray.reflect_dir(&tangent);
+        let reflected = ray.clone();
+
+        if offset {
+            let eps = M::Scalar::from_real(eps.clone());
+
+            // the ray's new direction belongs to the surface's tangent space, there's
+            // no side to move to, travel along the surface instead
+            match tangent.normal_towards(ray.dir.as_ref()) {
+                Some(normal) => ray.origin += normal.into_inner() * eps,
+                None => ray.advance(eps),
+            }
+        }
+
+        Some(reflected)
+    }
+}
+
+impl<'a, const D: usize, M: Mirror<D> + ?Sized> core::iter::FusedIterator
+    for PolicyRayPath<'a, D, M>
+{
+}
+
 /// Checks if adding `new_pt` to `path` results in a ray doing a potential infinite loop.
 /// `eps` is used for comparisons.
 #[inline]
@@ -567,3 +1161,561 @@ pub fn loop_index<const D: usize, S: ComplexField>(
         })
     })
 }
+
+/// Returns the maximum number of reflections a ray should perform, given its own cap, a
+/// scene-wide default, and a fallback (e.g. set by the user of a frontend), in that order
+/// of precedence. `None` means the ray may reflect indefinitely.
+///
+/// A cap of `Some(0)` is a valid cap, and is never overriden: the ray must not reflect at
+/// all, and, since it has no path, isn't considered to diverge either.
+///
+/// ```
+/// use miroir::resolve_reflection_cap;
+///
+/// // (per ray, scene default, fallback) => resolved
+/// let table = [
+///     ((Some(3), Some(5), Some(7)), Some(3)),
+///     ((Some(0), Some(5), Some(7)), Some(0)),
+///     ((None, Some(5), Some(7)), Some(5)),
+///     ((None, Some(0), Some(7)), Some(0)),
+///     ((None, None, Some(7)), Some(7)),
+///     ((None, None, None), None),
+/// ];
+///
+/// for ((per_ray, scene_default, fallback), resolved) in table {
+///     assert_eq!(resolve_reflection_cap(per_ray, scene_default, fallback), resolved);
+/// }
+/// ```
+#[inline]
+#[must_use]
+pub const fn resolve_reflection_cap(
+    per_ray: Option<usize>,
+    scene_default: Option<usize>,
+    fallback: Option<usize>,
+) -> Option<usize> {
+    match (per_ray, scene_default) {
+        (Some(cap), _) | (None, Some(cap)) => Some(cap),
+        (None, None) => fallback,
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use nalgebra::Vector2;
+    use std::vec::Vec;
+
+    /// An infinite line, reflective on both sides.
+    struct Wall {
+        point: SVector<f64, 2>,
+        normal: Unit<SVector<f64, 2>>,
+    }
+
+    impl Wall {
+        fn new(point: [f64; 2], normal: [f64; 2]) -> Self {
+            Self {
+                point: point.into(),
+                normal: Unit::new_normalize(normal.into()),
+            }
+        }
+    }
+
+    impl Mirror<2> for Wall {
+        type Scalar = f64;
+
+        fn add_tangents(&self, ctx: &mut SimulationCtx<f64, 2>) {
+            let ray = ctx.ray();
+            let denom = ray.dir.dot(&self.normal);
+            if denom != 0. {
+                let dist = (self.point - ray.origin).dot(&self.normal) / denom;
+                ctx.add_tangent(dist, Hyperplane::Normal(self.normal));
+            }
+        }
+    }
+
+    fn on_wall(wall: &Wall, p: &SVector<f64, 2>) -> bool {
+        (p - wall.point).dot(&wall.normal).abs() < 1e-9
+    }
+
+    const POLICIES: [ExclusionPolicy; 2] =
+        [ExclusionPolicy::EpsTravel, ExclusionPolicy::NormalOffset];
+
+    #[test]
+    fn rays_starting_on_a_mirror_dont_hit_it_again() {
+        let normal = [0.3, 0.7];
+        let walls = [Wall::new([0.1, 0.2], normal), Wall::new([1.1, 2.3], normal)];
+        let tangent = SVector::from([-normal[1], normal[0]]);
+
+        for policy in POLICIES {
+            for i in 0..1000 {
+                let s = f64::from(i) * 0.0137 - 7.;
+                // rounded, so the ray's origin is a tiny bit in front of, or behind, the wall
+                let origin = walls[0].point + tangent * s;
+                let ray = Ray::new(origin, [0.9, 0.4]);
+
+                let first = RayPath::new(ray, &walls, 1e-6)
+                    .with_policy(policy)
+                    .next()
+                    .unwrap();
+
+                assert!(on_wall(&walls[1], &first.origin), "{policy:?}, s = {s}");
+            }
+        }
+    }
+
+    #[test]
+    fn normal_offset_doesnt_tunnel_in_tight_corners() {
+        let eps = 1e-3;
+        let floor = Wall::new([0., 0.], [0., 1.]);
+        let side = Wall::new([0., 0.], [1., 0.]);
+        let walls = (floor, side);
+
+        // hits the floor at grazing distance (< eps) from the side wall
+        let ray = Ray::new([1.0005, 1.], [-1., -1.]);
+
+        let eps_travel = RayPath::new(ray.clone(), &walls, eps).collect::<Vec<_>>();
+        let normal_offset = RayPath::new(ray, &walls, eps)
+            .with_policy(ExclusionPolicy::NormalOffset)
+            .collect::<Vec<_>>();
+
+        // the side wall is skipped, the ray escapes through it
+        assert_eq!(eps_travel.len(), 1);
+        assert!(eps_travel[0].dir.x < 0.);
+
+        assert_eq!(normal_offset.len(), 2);
+        assert!(on_wall(&walls.0, &normal_offset[0].origin));
+        assert!(on_wall(&walls.1, &normal_offset[1].origin));
+        assert!(normal_offset[1].dir.x > 0. && normal_offset[1].dir.y > 0.);
+    }
+
+    #[test]
+    fn both_policies_agree_in_generic_scenes() {
+        let box_walls = [
+            Wall::new([0., 0.], [0., 1.]),
+            Wall::new([0., 0.], [1., 0.]),
+            Wall::new([3., 2.], [0., 1.]),
+            Wall::new([3., 2.], [1., 0.]),
+            Wall::new([1.5, 1.], [1., 1.3]),
+        ];
+
+        let ray = Ray::new([0.4, 0.3], [0.77, 0.31]);
+        let eps = 1e-10;
+
+        let eps_travel = RayPath::new(ray.clone(), &box_walls, eps).take(50);
+        let normal_offset = RayPath::new(ray, &box_walls, eps)
+            .with_policy(ExclusionPolicy::NormalOffset)
+            .take(50);
+
+        let mut count = 0;
+        for (a, b) in eps_travel.zip(normal_offset) {
+            assert!((a.origin - b.origin).norm() < 1e-6, "{a:?} != {b:?}");
+            assert!((a.dir.into_inner() - b.dir.into_inner()).norm() < 1e-6);
+            count += 1;
+        }
+
+        assert_eq!(count, 50);
+    }
+
+    #[test]
+    fn policy_ray_path_exposes_it_s_state() {
+        let wall = Wall::new([1., 0.], [1., 0.]);
+        let path = RayPath::new(Ray::new([0., 0.], [1., 0.]), &wall, 1e-6)
+            .with_policy(ExclusionPolicy::NormalOffset);
+
+        assert_eq!(path.policy(), ExclusionPolicy::NormalOffset);
+        assert_eq!(path.ray().origin, path.inner().ray.origin);
+
+        let mut path = path;
+        let reflected = path.next().unwrap();
+
+        // the yielded ray is at the point of reflection, the path's is moved away from it
+        assert_eq!(reflected.origin, SVector::from([1., 0.]));
+        assert!((path.ray().origin - SVector::from([1. - 1e-6, 0.])).norm() < 1e-12);
+    }
+
+    /// Adds a tangent at a fixed distance, whatever the ray.
+    struct Fixed {
+        dist: f64,
+        normal: Unit<SVector<f64, 2>>,
+    }
+
+    impl Fixed {
+        fn new(dist: f64, angle: f64) -> Self {
+            let (sin, cos) = angle.sin_cos();
+            Self {
+                dist,
+                normal: Unit::new_normalize([cos, sin].into()),
+            }
+        }
+
+        fn tangent(&self) -> Hyperplane<f64, 2> {
+            Hyperplane::Normal(self.normal)
+        }
+    }
+
+    impl Mirror<2> for Fixed {
+        type Scalar = f64;
+
+        fn add_tangents(&self, ctx: &mut SimulationCtx<f64, 2>) {
+            ctx.add_tangent(self.dist, self.tangent());
+        }
+    }
+
+    /// A part of `wall`, that reports the opposite normal, to be told apart from it.
+    struct Patch {
+        wall: Wall,
+        half_width: f64,
+    }
+
+    impl Mirror<2> for Patch {
+        type Scalar = f64;
+
+        fn add_tangents(&self, ctx: &mut SimulationCtx<f64, 2>) {
+            let ray = ctx.ray();
+            let Wall { point, normal } = &self.wall;
+            let denom = ray.dir.dot(normal);
+            if denom != 0. {
+                let dist = (point - ray.origin).dot(normal) / denom;
+                let offset = ray.at(dist) - point;
+                if offset.perp(normal).abs() <= self.half_width {
+                    ctx.add_tangent(dist, Hyperplane::Normal(-*normal));
+                }
+            }
+        }
+    }
+
+    fn selected_tangent(
+        mirror: &(impl Mirror<2, Scalar = f64> + ?Sized),
+        eps: f64,
+    ) -> Hyperplane<f64, 2> {
+        Ray::new([0., 0.], [1., 0.])
+            .closest_intersection(mirror, eps)
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn near_tie_chains_dont_depend_on_mirror_order() {
+        let eps = 1e-6;
+
+        // `a` and `b` are coincident, so are `b` and `c`, but not `a` and `c`
+        let a = || Prioritized(Fixed::new(1., 0.), 0);
+        let b = || Prioritized(Fixed::new(1. + 0.8 * eps, 1.), 1);
+        let c = || Prioritized(Fixed::new(1. + 1.6 * eps, 2.), 2);
+
+        let expected = b().0.tangent();
+
+        assert_eq!(selected_tangent(&(a(), b(), c()), eps), expected);
+        assert_eq!(selected_tangent(&(a(), c(), b()), eps), expected);
+        assert_eq!(selected_tangent(&(b(), a(), c()), eps), expected);
+        assert_eq!(selected_tangent(&(b(), c(), a()), eps), expected);
+        assert_eq!(selected_tangent(&(c(), a(), b()), eps), expected);
+        assert_eq!(selected_tangent(&(c(), b(), a()), eps), expected);
+    }
+
+    #[test]
+    fn patch_on_wall_wins_regardless_of_order() {
+        let eps = 1e-6;
+        let wall = || Wall::new([0.3, 0.1], [0.8, -0.35]);
+        let patch = || Patch {
+            wall: wall(),
+            half_width: 0.5,
+        };
+
+        let patch_first = (Prioritized(patch(), 1), wall());
+        let wall_first = (wall(), Prioritized(patch(), 1));
+
+        let Wall { point, normal } = wall();
+        let tangent = SVector::from([-normal.y, normal.x]);
+
+        for i in -100..=100 {
+            let s = f64::from(i) * 0.0123;
+            let target = point + tangent * s;
+            let origin = point + normal.into_inner() * 2. + tangent;
+            let ray = Ray::new(origin, target - origin);
+
+            let expected = if s.abs() < 0.5 - 1e-9 {
+                Hyperplane::Normal(-normal)
+            } else if s.abs() > 0.5 + 1e-9 {
+                Hyperplane::Normal(normal)
+            } else {
+                continue;
+            };
+
+            for scene in [&patch_first as &dyn Mirror<2, Scalar = f64>, &wall_first] {
+                // as used with `ExclusionPolicy::EpsTravel`
+                let (_, direction) = ray.closest_intersection(scene, eps).unwrap();
+                assert_eq!(direction, expected, "s = {s}");
+
+                // as used with `ExclusionPolicy::NormalOffset`
+                let (selected, _) = ray.closest_candidate(scene, 0., eps);
+                assert_eq!(selected.unwrap().tangent, expected, "s = {s}");
+            }
+        }
+    }
+
+    #[test]
+    fn without_priorities_the_closest_intersection_is_selected() {
+        let eps = 1e-6;
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+
+        for _ in 0..10_000 {
+            let mirrors: [Fixed; 5] = core::array::from_fn(|i| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                // clustered, so that many of them are within `eps` of each other
+                let dist = 1. + (state % 16) as f64 * 0.3 * eps;
+                Fixed::new(dist, i as f64)
+            });
+
+            let closest = mirrors
+                .iter()
+                .min_by(|a, b| a.dist.total_cmp(&b.dist))
+                .unwrap();
+
+            let (dist, direction) = Ray::new([0., 0.], [1., 0.])
+                .closest_intersection(&mirrors, eps)
+                .unwrap();
+
+            assert_eq!(dist, closest.dist);
+            // the first one added, among those at the same distance
+            assert_eq!(direction, closest.tangent());
+        }
+    }
+
+    #[test]
+    fn more_priorities_than_bands() {
+        let eps = 1e-6;
+
+        let mirrors =
+            || (0..10).map(|i| Prioritized(Fixed::new(1. + f64::from(i) * 0.05 * eps, 0.), i));
+
+        let forward = mirrors().collect::<Vec<_>>();
+        let backward = mirrors().rev().collect::<Vec<_>>();
+
+        let selected_priority = |mirrors: &[Prioritized<Fixed>]| {
+            let ray = Ray::new([0., 0.], [1., 0.]);
+            ray.closest_candidate(mirrors, eps, eps).0.unwrap().priority
+        };
+
+        // the farthest ones are dropped
+        assert_eq!(selected_priority(&forward), PRIORITY_BANDS as i32 - 1);
+        assert_eq!(selected_priority(&backward), PRIORITY_BANDS as i32 - 1);
+    }
+
+    /// `a` and `b` are the same angle, up to a multiple of `2π`.
+    fn same_angle(a: f64, b: f64) -> bool {
+        use core::f64::consts::TAU;
+        let diff = (a - b).rem_euclid(TAU);
+        diff < 1e-12 || TAU - diff < 1e-12
+    }
+
+    #[test]
+    fn polar_angles_round_trip_in_every_quadrant() {
+        use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+        for theta in [
+            0.,
+            FRAC_PI_4,
+            FRAC_PI_2,
+            3. * FRAC_PI_4,
+            PI,
+            -3. * FRAC_PI_4,
+            -FRAC_PI_2,
+            -FRAC_PI_4,
+        ] {
+            let v = Vector2::<f64>::from_angle(theta);
+
+            assert!((v.norm() - 1.).abs() < 1e-12);
+            assert!((v.polar_angle() - theta).abs() < 1e-12, "{theta}");
+            assert!(((v * 3.).polar_angle() - theta).abs() < 1e-12, "{theta}");
+        }
+    }
+
+    #[test]
+    fn rotations_add_to_polar_angles() {
+        use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+        let vectors = [
+            [2., 1.],
+            [-1., 3.],
+            [-2., -0.5],
+            [1., -4.],
+            [-1., 0.],
+            [0., -1.],
+        ];
+
+        for v in vectors.map(Vector2::<f64>::from) {
+            for theta in [FRAC_PI_4, FRAC_PI_2, PI, -FRAC_PI_2, -3. * FRAC_PI_4] {
+                let rotated = v.rotated(theta);
+
+                assert!((rotated.norm() - v.norm()).abs() < 1e-12);
+                assert!(same_angle(rotated.polar_angle(), v.polar_angle() + theta));
+                assert!(same_angle(v.angle_between(&rotated), theta));
+            }
+
+            assert!((v.rotated(FRAC_PI_2) - v.perpendicular()).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn polar_angles_are_never_minus_pi() {
+        use core::f64::consts::PI;
+
+        // `atan2` returns `-π` for these
+        assert_eq!(Vector2::new(-1., -0.).polar_angle(), PI);
+        assert_eq!(Vector2::new(-0., -0.).polar_angle(), PI);
+
+        let (u, v) = (Vector2::new(1., 0.), Vector2::new(-1., -0.));
+        assert_eq!(u.angle_between(&v), PI);
+        assert_eq!(v.angle_between(&u), PI);
+    }
+
+    #[cfg(feature = "trace")]
+    mod logging {
+        use super::*;
+        use log::{Level, LevelFilter, Log, Metadata, Record};
+        use std::{cell::Cell, cell::RefCell, string::String, string::ToString, sync::Once};
+
+        /// Captures the records logged by the current thread, tests run in parallel.
+        struct Capture;
+
+        std::thread_local! {
+            static LEVEL: Cell<LevelFilter> = const { Cell::new(LevelFilter::Off) };
+            static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+        }
+
+        impl Log for Capture {
+            fn enabled(&self, metadata: &Metadata) -> bool {
+                metadata.level() <= LEVEL.get()
+            }
+
+            fn log(&self, record: &Record) {
+                if self.enabled(record.metadata()) {
+                    let message = record.args().to_string();
+                    RECORDS.with_borrow_mut(|records| records.push((record.level(), message)));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        /// Returns the records logged by `f`, at `level`, or more severe.
+        fn capture(level: LevelFilter, f: impl FnOnce()) -> Vec<(Level, String)> {
+            static INIT: Once = Once::new();
+            INIT.call_once(|| {
+                log::set_logger(&Capture).unwrap();
+                log::set_max_level(LevelFilter::Trace);
+            });
+
+            LEVEL.set(level);
+            f();
+            LEVEL.set(LevelFilter::Off);
+            RECORDS.take()
+        }
+
+        /// Adds a tangent at a NaN distance.
+        struct Degenerate;
+
+        impl Mirror<2> for Degenerate {
+            type Scalar = f64;
+
+            fn add_tangents(&self, ctx: &mut SimulationCtx<f64, 2>) {
+                let normal = Unit::new_normalize([1., 0.].into());
+                ctx.add_tangent(f64::NAN, Hyperplane::Normal(normal));
+            }
+        }
+
+        fn box_walls() -> [Wall; 4] {
+            [
+                Wall::new([0., 0.], [0., 1.]),
+                Wall::new([0., 0.], [1., 0.]),
+                Wall::new([3., 2.], [0., 1.]),
+                Wall::new([3., 2.], [1., 0.]),
+            ]
+        }
+
+        #[test]
+        fn nan_discards_are_reported_once_per_path() {
+            let scene = (Wall::new([1., 0.], [1., 0.]), Degenerate);
+
+            for policy in POLICIES {
+                let mut path =
+                    RayPath::new(Ray::new([0., 0.], [1., 0.3]), &scene, 1e-6).with_policy(policy);
+
+                let records = capture(LevelFilter::Info, || {
+                    assert_eq!(path.by_ref().count(), 1);
+                    // the path is fused, it doesn't report again
+                    assert!(path.next().is_none());
+                });
+
+                // once per call to `next`
+                assert_eq!(path.nan_discarded(), 2);
+                assert_eq!(records.len(), 1, "{records:?}");
+                assert_eq!(records[0].0, Level::Warn);
+                assert!(records[0].1.contains("discarded 2 intersections"));
+            }
+        }
+
+        #[test]
+        fn paths_without_nan_discards_dont_warn() {
+            let walls = box_walls();
+
+            let records = capture(LevelFilter::Trace, || {
+                let mut path = RayPath::new(Ray::new([1., 1.], [1., 0.]), &walls[2..], 1e-6)
+                    .with_policy(ExclusionPolicy::EpsTravel);
+                assert_eq!(path.by_ref().count(), 1);
+                assert_eq!(path.nan_discarded(), 0);
+            });
+
+            assert!(records.iter().all(|(level, _)| *level == Level::Trace));
+        }
+
+        #[test]
+        fn hot_path_is_silent_at_default_levels() {
+            let walls = box_walls();
+            let ray = Ray::new([0.4, 0.3], [0.77, 0.31]);
+
+            let records = capture(LevelFilter::Debug, || {
+                let path = RayPath::new(ray.clone(), &walls, 1e-6);
+                assert_eq!(path.take(100).count(), 100);
+
+                for policy in POLICIES {
+                    let path = RayPath::new(ray.clone(), &walls, 1e-6).with_policy(policy);
+                    assert_eq!(path.take(100).count(), 100);
+                }
+            });
+
+            assert!(records.is_empty(), "{records:?}");
+        }
+
+        #[test]
+        fn reflections_are_traced() {
+            let walls = box_walls();
+            let ray = Ray::new([0.4, 0.3], [0.77, 0.31]);
+
+            for policy in POLICIES {
+                let records = capture(LevelFilter::Trace, || {
+                    let path = RayPath::new(ray.clone(), &walls, 1e-6).with_policy(policy);
+                    assert_eq!(path.take(10).count(), 10);
+                });
+
+                assert_eq!(records.len(), 10, "{records:?}");
+                for (level, message) in records {
+                    assert_eq!(level, Level::Trace);
+                    assert!(message.starts_with("reflected at"));
+                }
+            }
+
+            let records = capture(LevelFilter::Trace, || {
+                let _ = Ray::new([0., 0.], [1., 0.]).closest_intersection(&Degenerate, 1e-6);
+            });
+
+            assert_eq!(records.len(), 1, "{records:?}");
+            assert_eq!(records[0].1, "discarded an intersection at a NaN distance");
+        }
+    }
+}
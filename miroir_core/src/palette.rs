@@ -0,0 +1,135 @@
+//! Color palettes, distinguishable by people with color vision deficiencies, used by
+//! frontends to automatically assign colors to rays that don't specify one.
+
+#[cfg(any(feature = "std", feature = "libm"))]
+use nalgebra::ComplexField;
+
+/// An sRGB color, with 8 bits per channel.
+pub type Rgb = [u8; 3];
+
+/// The near-black background palettes are designed to be drawn on. `miroir_glium` clears the
+/// screen with it, and `miroir_numworks`' examples fill the screen with it.
+pub const BACKGROUND: Rgb = [3, 3, 13];
+
+/// A named, finite, list of colors.
+///
+/// All colors of all palettes have a contrast ratio (see [`contrast_ratio`]) of at least
+/// `3`, WCAG's minimum for graphical objects, with [`BACKGROUND`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Palette {
+    /// The Okabe-Ito palette, designed to be unambiguous to people with all common forms of
+    /// color blindness, without it's black entry.
+    OkabeIto,
+    /// The 5 lightest of 8 evenly spaced samples of the viridis color map. Colors change
+    /// gradually (and monotonically in lightness) with the index of the ray.
+    Viridis,
+    /// Fully saturated colors, along with white, for maximum contrast with dark backgrounds.
+    HighContrast,
+}
+
+impl Palette {
+    /// All palettes.
+    pub const ALL: [Self; 3] = [Self::OkabeIto, Self::Viridis, Self::HighContrast];
+
+    /// The colors of this palette.
+    #[inline]
+    #[must_use]
+    pub const fn colors(self) -> &'static [Rgb] {
+        match self {
+            Self::OkabeIto => &[
+                [230, 159, 0],
+                [86, 180, 233],
+                [0, 158, 115],
+                [240, 228, 66],
+                [0, 114, 178],
+                [213, 94, 0],
+                [204, 121, 167],
+            ],
+            Self::Viridis => &[
+                [39, 127, 142],
+                [31, 161, 135],
+                [74, 193, 109],
+                [160, 218, 57],
+                [253, 231, 37],
+            ],
+            Self::HighContrast => &[
+                [255, 255, 255],
+                [255, 255, 0],
+                [0, 255, 255],
+                [255, 0, 255],
+                [0, 255, 0],
+                [255, 128, 0],
+            ],
+        }
+    }
+
+    /// The color assigned to the `i`-th ray. Palettes wrap around when there are more rays
+    /// than colors.
+    #[inline]
+    #[must_use]
+    pub const fn color(self, i: usize) -> Rgb {
+        let colors = self.colors();
+        colors[i % colors.len()]
+    }
+}
+
+/// Returns the relative luminance of `color`, as defined by WCAG 2, `0` for black,
+/// `1` for white.
+#[cfg(any(feature = "std", feature = "libm"))]
+#[must_use]
+pub fn relative_luminance(color: Rgb) -> f64 {
+    let linearize = |c: u8| {
+        let c = f64::from(c) / 255.;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ComplexField::powf((c + 0.055) / 1.055, 2.4)
+        }
+    };
+
+    let [r, g, b] = color.map(linearize);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Returns the contrast ratio between `a` and `b`, as defined by WCAG 2, from `1`
+/// (identical luminance) to `21` (black on white).
+///
+/// WCAG recommends a ratio of at least `3` for graphical objects.
+#[cfg(any(feature = "std", feature = "libm"))]
+#[must_use]
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettes_contrast_with_background() {
+        for palette in Palette::ALL {
+            for &color in palette.colors() {
+                let ratio = contrast_ratio(color, BACKGROUND);
+                assert!(
+                    ratio >= 3.,
+                    "{palette:?}: {color:?} has a contrast of {ratio}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn background_matches_former_glium_clear_color() {
+        let clear_color = [0.01f64, 0.01, 0.05].map(|c| (c * 255.).round() as u8);
+        assert_eq!(clear_color, BACKGROUND);
+    }
+
+    #[test]
+    fn contrast_ratio_extremes() {
+        assert!((contrast_ratio([0; 3], [255; 3]) - 21.).abs() < 1e-9);
+        assert!((contrast_ratio([255; 3], [0; 3]) - 21.).abs() < 1e-9);
+        assert_eq!(contrast_ratio(BACKGROUND, BACKGROUND), 1.);
+    }
+}
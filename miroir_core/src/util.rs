@@ -0,0 +1,336 @@
+//! Small, allocation-free, helpers for frontends, that don't depend on a specific platform.
+
+/// The maximum number of decimals [`fmt_fixed`] can write, larger values are clamped to it.
+pub const MAX_FIXED_DECIMALS: u8 = 31;
+
+/// A cursor writing ASCII bytes into a fixed buffer, failing when it's full.
+struct Cursor<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Cursor<'_> {
+    fn push(&mut self, b: u8) -> Option<()> {
+        *self.buf.get_mut(self.len)? = b;
+        self.len += 1;
+        Some(())
+    }
+
+    fn push_str(&mut self, s: &[u8]) -> Option<()> {
+        s.iter().try_for_each(|&b| self.push(b))
+    }
+
+    /// Writes `n` in decimal, left-padded with zeros to at least `width` digits.
+    fn push_digits(&mut self, mut n: u128, width: usize) -> Option<()> {
+        let mut digits = 1;
+        let mut rest = n / 10;
+        while rest > 0 {
+            digits += 1;
+            rest /= 10;
+        }
+
+        let digits = digits.max(width);
+        let out = self.buf.get_mut(self.len..self.len + digits)?;
+
+        for b in out.iter_mut().rev() {
+            *b = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+
+        self.len += digits;
+        Some(())
+    }
+}
+
+/// Writes the exact decimal expansion of `value`, rounded (half to even) to `decimals`
+/// decimals, `decimals` must not exceed [`MAX_FIXED_DECIMALS`].
+fn write_fixed(value: f32, decimals: u8, out: &mut Cursor) -> Option<()> {
+    if value.is_nan() {
+        return out.push_str(b"NaN");
+    }
+
+    if value.is_sign_negative() {
+        out.push(b'-')?;
+    }
+
+    if value.is_infinite() {
+        return out.push_str(b"inf");
+    }
+
+    // |value| = mantissa * 2^exp, exactly
+    let bits = value.to_bits();
+    let (exp_bits, frac_bits) = ((bits >> 23) & 0xff, bits & 0x7f_ffff);
+    let (mantissa, exp) = if exp_bits == 0 {
+        (frac_bits, -149)
+    } else {
+        (frac_bits | 0x80_0000, exp_bits as i32 - 150)
+    };
+    let mantissa = u128::from(mantissa);
+
+    let one = 10u128.pow(decimals.into());
+
+    let (mut int, mut frac) = if exp >= 0 {
+        // f32::MAX < 2^128, this can't overflow
+        (mantissa << exp, 0)
+    } else {
+        // |value| = int + rem / 2^k
+        let k = exp.unsigned_abs();
+        let (int, rem) = if k < 128 {
+            (mantissa >> k, mantissa & ((1 << k) - 1))
+        } else {
+            (0, mantissa)
+        };
+
+        // rem < 2^24 and one <= 10^31, so this fits in 127 bits
+        let scaled = rem * one;
+
+        if k >= 128 {
+            // scaled < 2^127 <= 2^(k - 1), rounds down to zero
+            (int, 0)
+        } else {
+            let (frac, r) = (scaled >> k, scaled & ((1 << k) - 1));
+            let half = 1 << (k - 1);
+
+            let last_digit_odd = if decimals == 0 { int & 1 } else { frac & 1 } == 1;
+
+            if r > half || (r == half && last_digit_odd) {
+                (int, frac + 1)
+            } else {
+                (int, frac)
+            }
+        }
+    };
+
+    // rounding up may carry into the integer part
+    if frac == one {
+        frac = 0;
+        int += 1;
+    }
+
+    out.push_digits(int, 1)?;
+
+    if decimals > 0 {
+        out.push(b'.')?;
+        out.push_digits(frac, decimals.into())?;
+    }
+
+    Some(())
+}
+
+/// Formats `value` with exactly `decimals` decimals (at most [`MAX_FIXED_DECIMALS`]) into
+/// `buf`, followed by a NUL byte, without allocating, or depending on `core::fmt`.
+///
+/// The output matches that of `format!("{value:.decimals$}")`, (e. g. `"-1.25"`, `"0.0"`,
+/// `"-0.0"`, `"NaN"`, `"inf"` or `"-inf"`): `value`'s exact decimal expansion is rounded,
+/// with ties rounded to even. `buf` can then be passed as is to C string drawing functions.
+///
+/// Values that don't fit in `buf` (with it's terminating NUL byte) with `decimals` decimals
+/// are written with as many decimals as fit instead (e. g. `"1.000"` for `1.` with 5
+/// decimals, in a 6 byte buffer). Finite values whose integer part doesn't fit are clamped
+/// to the largest value that does, followed by a `+` (e. g. `"9999+"` or `"-999+"` for a
+/// 6 byte buffer). Returns an empty string if even that doesn't fit, or if `buf` is too small
+/// for `"NaN"`, `"inf"`, or `"-inf"`.
+pub fn fmt_fixed<const N: usize>(value: f32, decimals: u8, buf: &mut [u8; N]) -> &str {
+    // reserve the last byte for the terminating NUL
+    let capacity = N.saturating_sub(1);
+
+    let mut out = Cursor {
+        buf: &mut buf[..capacity],
+        len: 0,
+    };
+
+    // drop decimals until the value fits
+    let fits = (0..=decimals.min(MAX_FIXED_DECIMALS))
+        .rev()
+        .any(|decimals| {
+            out.len = 0;
+            write_fixed(value, decimals, &mut out).is_some()
+        });
+
+    if !fits {
+        out.len = 0;
+
+        let sign = usize::from(value.is_sign_negative());
+
+        if value.is_finite() && capacity >= sign + 2 {
+            if sign == 1 {
+                out.buf[0] = b'-';
+            }
+
+            out.buf[sign..capacity - 1].fill(b'9');
+            out.buf[capacity - 1] = b'+';
+            out.len = capacity;
+        }
+    }
+
+    let len = out.len;
+
+    if let Some(nul) = buf.get_mut(len) {
+        *nul = 0;
+    }
+
+    // only ASCII characters are ever written
+    core::str::from_utf8(&buf[..len]).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::{format, string::String};
+
+    fn expected(value: f32, decimals: u8) -> String {
+        format!("{value:.*}", usize::from(decimals))
+    }
+
+    fn check(value: f32, decimals: u8) {
+        let mut buf = [0xff; 128];
+        let s = fmt_fixed(value, decimals, &mut buf);
+        assert_eq!(
+            s,
+            expected(value, decimals),
+            "{value:e} ({:#010x}) with {decimals} decimals",
+            value.to_bits(),
+        );
+        assert_eq!(buf[s.len()], 0);
+    }
+
+    const EDGE_CASES: &[f32] = &[
+        0.,
+        -0.,
+        1.,
+        -1.,
+        0.5,
+        1.5,
+        2.5,
+        -2.5,
+        0.125,
+        0.375,
+        1.0e-7,
+        0.1,
+        0.05,
+        9.5,
+        99.5,
+        999.999_94,
+        123_456.79,
+        f32::EPSILON,
+        f32::MIN_POSITIVE,
+        -f32::MIN_POSITIVE,
+        f32::MAX,
+        f32::MIN,
+        f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+    ];
+
+    #[test]
+    fn edge_cases_match_format() {
+        for &value in EDGE_CASES {
+            for decimals in 0..=MAX_FIXED_DECIMALS {
+                check(value, decimals);
+            }
+        }
+    }
+
+    #[test]
+    fn ties_round_to_even() {
+        let mut buf = [0; 16];
+        assert_eq!(fmt_fixed(0.5, 0, &mut buf), "0");
+        assert_eq!(fmt_fixed(1.5, 0, &mut buf), "2");
+        assert_eq!(fmt_fixed(2.5, 0, &mut buf), "2");
+        assert_eq!(fmt_fixed(-2.5, 0, &mut buf), "-2");
+        assert_eq!(fmt_fixed(0.125, 2, &mut buf), "0.12");
+        assert_eq!(fmt_fixed(0.375, 2, &mut buf), "0.38");
+        // rounding up carries into the integer part
+        assert_eq!(fmt_fixed(9.5, 0, &mut buf), "10");
+        assert_eq!(fmt_fixed(99.96, 1, &mut buf), "100.0");
+    }
+
+    #[test]
+    fn subnormals_match_format() {
+        for bits in (1..0x80_0000).step_by(0x1_0001).chain([1, 2, 3, 0x7f_ffff]) {
+            for value in [f32::from_bits(bits), -f32::from_bits(bits)] {
+                for decimals in [0, 1, 10, MAX_FIXED_DECIMALS] {
+                    check(value, decimals);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_bits_match_format() {
+        // xorshift32, for reproducibility
+        let mut state = 0x2545_f491_u32;
+
+        for _ in 0..100_000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+
+            check(f32::from_bits(state), (state % 12) as u8);
+        }
+    }
+
+    #[test]
+    fn special_values() {
+        let mut buf = [0; 8];
+        assert_eq!(fmt_fixed(-0., 1, &mut buf), "-0.0");
+        assert_eq!(fmt_fixed(-0., 0, &mut buf), "-0");
+        assert_eq!(fmt_fixed(f32::NAN, 3, &mut buf), "NaN");
+        assert_eq!(fmt_fixed(-f32::NAN, 3, &mut buf), "NaN");
+        assert_eq!(fmt_fixed(f32::INFINITY, 3, &mut buf), "inf");
+        assert_eq!(fmt_fixed(f32::NEG_INFINITY, 3, &mut buf), "-inf");
+    }
+
+    #[test]
+    fn decimals_are_clamped() {
+        let mut buf = [0; 64];
+        let clamped = String::from(fmt_fixed(0.1, MAX_FIXED_DECIMALS, &mut buf));
+        assert_eq!(fmt_fixed(0.1, u8::MAX, &mut buf), clamped);
+    }
+
+    #[test]
+    fn small_buffers() {
+        assert_eq!(fmt_fixed(1., 0, &mut []), "");
+
+        let mut buf = [0xff; 1];
+        assert_eq!(fmt_fixed(1., 0, &mut buf), "");
+        assert_eq!(buf, [0]);
+
+        let mut buf = [0xff; 2];
+        assert_eq!(fmt_fixed(1., 0, &mut buf), "1");
+        assert_eq!(buf, [b'1', 0]);
+    }
+
+    #[test]
+    fn overflowing_values_are_clamped() {
+        let mut buf = [0; 6];
+        assert_eq!(fmt_fixed(123_456., 0, &mut buf), "9999+");
+        assert_eq!(fmt_fixed(-123_456., 0, &mut buf), "-999+");
+        assert_eq!(fmt_fixed(f32::MAX, 5, &mut buf), "9999+");
+        assert_eq!(buf[5], 0);
+
+        // values that fit aren't clamped
+        assert_eq!(fmt_fixed(99_999., 0, &mut buf), "99999");
+        assert_eq!(fmt_fixed(-9_999., 0, &mut buf), "-9999");
+
+        // values that only fit with fewer decimals lose some instead
+        assert_eq!(fmt_fixed(1., 5, &mut buf), "1.000");
+        assert_eq!(fmt_fixed(-1.25, 5, &mut buf), "-1.25");
+        assert_eq!(fmt_fixed(-1.5, 5, &mut buf), "-1.50");
+        assert_eq!(fmt_fixed(9.999_99, 5, &mut buf), "10.00");
+        assert_eq!(fmt_fixed(12_345.6, 2, &mut buf), "12346");
+        assert_eq!(fmt_fixed(-1_234.5, 2, &mut buf), "-1234");
+        assert_eq!(buf[5], 0);
+
+        // non-finite values aren't clamped, and can be too long too
+        let mut buf = [0; 3];
+        assert_eq!(fmt_fixed(f32::NAN, 0, &mut buf), "");
+        assert_eq!(fmt_fixed(f32::INFINITY, 0, &mut buf), "");
+
+        // even the clamp doesn't fit
+        assert_eq!(fmt_fixed(-10., 0, &mut buf), "");
+        assert_eq!(fmt_fixed(100., 0, &mut buf), "9+");
+    }
+}
@@ -1,8 +1,8 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::Cylinder;
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let mirror = Cylinder::new([0., 0., 0.], [10., 0., 0.], 2.);
     let rays = [SimulationRay::new([0., 1., 0.], [0.004, 1., 0.01])];
-    SimulationWindow::default().run(&mirror, rays, SimulationParams::default());
+    SimulationWindow::default().run(&mirror, rays, SimulationParams::default())
 }
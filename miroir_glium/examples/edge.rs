@@ -1,8 +1,9 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use miroir::Ray;
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::Simplex;
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let mirror = Simplex::new([[1., 0.000001], [1., 1.]]);
-    let rays = [SimulationRay::new([0., 0.], [1., 0.])];
+    let rays = [SimulationRay::from(Ray::from_angle([0., 0.], 0.))];
     SimulationWindow::default().run(&mirror, rays, SimulationParams::default())
 }
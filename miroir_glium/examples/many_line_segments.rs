@@ -1,7 +1,8 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use miroir::palette::Palette;
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::LineSegment;
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let mirrors = [
         LineSegment::new([[-3.306, -3.677], [-6.23, 0.08]]),
         LineSegment::new([[-2.385, -3.54], [0.634, -0.136]]),
@@ -43,7 +44,23 @@ fn main() {
         LineSegment::new([[0.455, -3.26], [-2.605, -3.38]]),
     ];
 
-    let rays = [SimulationRay::new([-1., 0.], [1., 1.6])];
+    // a fan of rays, each automatically assigned a distinct color
+    let rays = [
+        [1., 1.6],
+        [1., 1.2],
+        [1., 2.],
+        [-1., 1.6],
+        [1., -1.],
+        [-1., -0.5],
+    ]
+    .map(|dir| SimulationRay::new([-1., 0.], dir));
 
-    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default());
+    SimulationWindow::default().run(
+        &mirrors,
+        rays,
+        SimulationParams {
+            palette: Some(Palette::OkabeIto),
+            ..Default::default()
+        },
+    )
 }
@@ -1,7 +1,7 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::LineSegment;
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let mirrors = [
         LineSegment::new([[1., -1.], [1., 1.]]),
         LineSegment::new([[-1., -1.], [1., 1.]]),
@@ -0,0 +1,25 @@
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
+use miroir_shapes::{LineSegment, Sphere};
+
+fn main() -> Result<(), SimulationError> {
+    // The same scene as `miroir_numworks`'s `trapped_circle` example
+    const TOP_LEFT: [f64; 2] = [-1.6, 1.1];
+    const BOTTOM_RIGHT: [f64; 2] = [1.6, -1.1];
+    const BOTTOM_LEFT: [f64; 2] = [-1.6, -1.1];
+    const TOP_RIGHT: [f64; 2] = [1.6, 1.1];
+    const CENTER: [f64; 2] = [0., 0.];
+
+    let mirrors = (
+        Sphere::new(CENTER, 0.5),
+        [
+            LineSegment::new([TOP_LEFT, TOP_RIGHT]),
+            LineSegment::new([TOP_LEFT, BOTTOM_LEFT]),
+            LineSegment::new([BOTTOM_RIGHT, TOP_RIGHT]),
+            LineSegment::new([BOTTOM_RIGHT, BOTTOM_LEFT]),
+        ],
+    );
+
+    let rays = [SimulationRay::new([-1.5, 0.8], [2., -1.]).with_reflection_cap(200)];
+
+    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default())
+}
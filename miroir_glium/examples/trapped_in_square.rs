@@ -1,7 +1,7 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::LineSegment;
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let mirrors = [
         LineSegment::new([[1., 0.], [0., 1.]]),
         LineSegment::new([[0., 1.], [-1., 0.]]),
@@ -11,5 +11,5 @@ fn main() {
 
     let rays = [SimulationRay::new([0.5, 0.33], [1., 1.1])];
 
-    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default());
+    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default())
 }
@@ -1,7 +1,10 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use core::f64::consts::FRAC_PI_4;
+
+use miroir::Ray;
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::{LineSegment, Sphere};
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let mirrors = (
         Sphere::new([4., 0.], 1.),
         [
@@ -13,10 +16,11 @@ fn main() {
     );
 
     let rays = [
-        SimulationRay::new([0., 0.], [1., 1.]),
-        SimulationRay::new([0.25, 0.5], [1., 0.]),
-        SimulationRay::new([4., 0.5], [1., 0.]),
-    ];
+        Ray::from_angle([0., 0.], FRAC_PI_4),
+        Ray::from_angle([0.25, 0.5], 0.),
+        Ray::from_angle([4., 0.5], 0.),
+    ]
+    .map(SimulationRay::from);
 
-    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default());
+    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default())
 }
@@ -1,7 +1,7 @@
-use miroir_glium::{SimulationParams, SimulationRay, SimulationWindow};
+use miroir_glium::{SimulationError, SimulationParams, SimulationRay, SimulationWindow};
 use miroir_shapes::{Sphere, Triangle};
 
-fn main() {
+fn main() -> Result<(), SimulationError> {
     let max = std::env::args()
         .nth(1)
         .map(|s| s.parse().expect("expected a positive integer"))
@@ -36,5 +36,5 @@ fn main() {
 
     let rays = [SimulationRay::new([4., 3., 0.1], [-1., -1., 0.]).with_reflection_cap(max)];
 
-    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default());
+    SimulationWindow::default().run(&mirrors, rays, SimulationParams::default())
 }
@@ -1,11 +1,26 @@
 use super::*;
 
 use core::{f32::consts::FRAC_PI_2, time::Duration};
-use glium::glutin::event::{ElementState, VirtualKeyCode};
 use nalgebra::{Matrix4, Point3, Vector3};
+use winit::{event::ElementState, keyboard::KeyCode};
 
 const SAFE_FRAC_PI_2: f32 = FRAC_PI_2 - 0.0001;
 
+/// Converts a raw mouse motion delta, reported in physical pixels, to logical pixels,
+/// making mouse-look speed independent of the window's scale factor.
+///
+/// Non-positive or non-finite scale factors are ignored.
+#[inline]
+pub fn normalize_mouse_delta((dx, dy): (f64, f64), scale_factor: f64) -> (f32, f32) {
+    let scale = if scale_factor.is_finite() && scale_factor > 0. {
+        scale_factor
+    } else {
+        1.
+    };
+
+    ((dx / scale) as f32, (dy / scale) as f32)
+}
+
 pub struct Camera {
     pos: Point3<f32>,
     yaw: f32,
@@ -16,11 +31,15 @@ impl Camera {
     pub fn new(position: impl Into<Point3<f32>>, yaw: f32, pitch: f32) -> Self {
         Self {
             pos: position.into(),
-            yaw: yaw.into(),
-            pitch: pitch.into(),
+            yaw,
+            pitch,
         }
     }
 
+    pub const fn position(&self) -> &Point3<f32> {
+        &self.pos
+    }
+
     pub fn calc_matrix(&self) -> Matrix4<f32> {
         let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
         let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
@@ -63,35 +82,38 @@ impl CameraController {
     }
 
     #[rustfmt::skip]
-    pub fn process_keyboard(&mut self, key: VirtualKeyCode, state: ElementState) -> bool {
+    pub fn process_keyboard(&mut self, key: KeyCode, state: ElementState) -> bool {
         const RATIO: f32 = 0.8;
 
-        use VirtualKeyCode::*;
+        use KeyCode::*;
 
         let amount = (state == ElementState::Pressed) as u32 as f32;
 
         let mut res = true;
 
         match key {
-            Z | W  => self.amount_forward = amount,
-            S      => self.amount_backwards = amount,
-            Q | A  => self.amount_left = amount,
-            D      => self.amount_right = amount,
-            Space  => self.amount_up = amount,
-            LShift => self.amount_down = amount,
-            Up     => self.speed /= RATIO,
-            Down   => self.speed *= RATIO,
-            Right  => self.mouse_sensitivity /= RATIO,
-            Left   => self.mouse_sensitivity *= RATIO,
+            // physical key positions, this is ZQSD on AZERTY keyboards
+            KeyW       => self.amount_forward = amount,
+            KeyS       => self.amount_backwards = amount,
+            KeyA       => self.amount_left = amount,
+            KeyD       => self.amount_right = amount,
+            Space      => self.amount_up = amount,
+            ShiftLeft  => self.amount_down = amount,
+            ArrowUp    => self.speed /= RATIO,
+            ArrowDown  => self.speed *= RATIO,
+            ArrowRight => self.mouse_sensitivity /= RATIO,
+            ArrowLeft  => self.mouse_sensitivity *= RATIO,
             _ => res = false,
         }
 
         res
     }
 
-    pub fn set_mouse_delta(&mut self, mouse_dx: f64, mouse_dy: f64) {
-        self.rotate_horizontal = mouse_dx as f32;
-        self.rotate_vertical = -mouse_dy as f32;
+    /// Accumulates a mouse motion delta, in logical pixels (see [`normalize_mouse_delta`]),
+    /// until the next call to [`Self::update_camera`].
+    pub fn add_mouse_delta(&mut self, (mouse_dx, mouse_dy): (f32, f32)) {
+        self.rotate_horizontal += mouse_dx;
+        self.rotate_vertical -= mouse_dy;
     }
 
     pub fn update_camera(&mut self, camera: &mut Camera, dt: Duration) {
@@ -118,3 +140,53 @@ impl CameraController {
         self.rotate_vertical = 0.;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mouse_delta_is_converted_to_logical_pixels() {
+        assert_eq!(normalize_mouse_delta((4., -6.), 1.), (4., -6.));
+        assert_eq!(normalize_mouse_delta((4., -6.), 2.), (2., -3.));
+        assert_eq!(normalize_mouse_delta((3., 3.), 1.5), (2., 2.));
+    }
+
+    #[test]
+    fn invalid_scale_factors_are_ignored() {
+        for scale_factor in [0., -2., f64::NAN, f64::INFINITY] {
+            assert_eq!(normalize_mouse_delta((4., -6.), scale_factor), (4., -6.));
+        }
+    }
+
+    #[test]
+    fn mouse_look_is_independent_of_scale_factor() {
+        let look = |delta, scale_factor| {
+            let mut camera = Camera::new([0., 0., 0.], 0., 0.);
+            let mut controller = CameraController::new(1., 1.);
+            controller.add_mouse_delta(normalize_mouse_delta(delta, scale_factor));
+            controller.update_camera(&mut camera, Duration::from_millis(100));
+            (camera.yaw, camera.pitch)
+        };
+
+        // the same physical motion, on a regular, and a HiDPI screen
+        assert_eq!(look((10., 5.), 1.), look((20., 10.), 2.));
+        assert_ne!(look((10., 5.), 1.), look((10., 5.), 2.));
+    }
+
+    #[test]
+    fn mouse_deltas_accumulate_until_update() {
+        let mut camera = Camera::new([0., 0., 0.], 0., 0.);
+        let mut controller = CameraController::new(1., 1.);
+
+        controller.add_mouse_delta((1., 2.));
+        controller.add_mouse_delta((3., -1.));
+        controller.update_camera(&mut camera, Duration::from_secs(1));
+
+        assert_eq!((camera.yaw, camera.pitch), (4., -1.));
+
+        // and are reset afterwards
+        controller.update_camera(&mut camera, Duration::from_secs(1));
+        assert_eq!((camera.yaw, camera.pitch), (4., -1.));
+    }
+}
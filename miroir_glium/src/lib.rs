@@ -1,27 +1,39 @@
 use core::{
-    array,
+    array, fmt,
+    num::NonZeroU32,
     ops::{Add, Deref, Mul},
 };
 extern crate alloc;
 use alloc::{boxed::Box, collections::TryReserveError, rc::Rc, sync::Arc, vec::Vec};
 use num_traits::{float::FloatCore, AsPrimitive};
-use std::time;
+use std::{error::Error, time};
 
-use gl::{backend::glutin::DisplayCreationError, glutin};
+use gl::{glutin, IncompatibleOpenGl};
 
-use glutin::{dpi, event_loop, window};
+use glutin::{
+    config::{ConfigTemplateBuilder, GlConfig},
+    context::ContextAttributesBuilder,
+    display::{GetGlDisplay, GlDisplay},
+    prelude::NotCurrentGlContext,
+    surface::{GlSurface, SurfaceAttributesBuilder, SwapInterval, WindowSurface},
+};
+use glutin_winit::DisplayBuilder;
 use miroir::*;
 use nalgebra::{ComplexField, RealField, SVector, Scalar, Unit};
+use raw_window_handle::HasRawWindowHandle;
+use winit::{dpi, error::EventLoopError, event_loop, window};
 
 mod camera;
 mod renderable;
 mod sim_render_data;
+mod tessellation;
 
 use sim_render_data::SimulationRenderData;
 
-pub use renderable::*;
 pub use glium as gl;
-pub use glium_shapes as gl_shapes;
+pub use renderable::*;
+pub use tessellation::*;
+pub use winit;
 
 /// The main vertex type used when rendering simulations,
 /// You are free to use whichever vertex type you wish, as long as their dimensions
@@ -67,6 +79,28 @@ impl<const D: usize> Mul<Vertex<D>> for f32 {
     }
 }
 
+impl<const D: usize> Vertex<D> {
+    /// This vertex's position in 3D space, (`z = 0` in 2D).
+    pub(crate) fn position_3d(&self) -> [f32; 3] {
+        array::from_fn(|i| self.position.get(i).copied().unwrap_or(0.))
+    }
+}
+
+/// Returns the opposite corners of the smallest axis-aligned box containing `points`,
+/// `None` if there are none.
+pub(crate) fn bounding_box(points: impl IntoIterator<Item = [f32; 3]>) -> Option<[[f32; 3]; 2]> {
+    let mut points = points.into_iter();
+
+    let first = points.next()?;
+
+    Some(points.fold([first, first], |[min, max], p| {
+        [
+            array::from_fn(|i| min[i].min(p[i])),
+            array::from_fn(|i| max[i].max(p[i])),
+        ]
+    }))
+}
+
 pub type Vertex2D = Vertex<2>;
 gl::implement_vertex!(Vertex2D, position);
 
@@ -92,12 +126,21 @@ pub struct SimulationRay<S, const D: usize> {
     pub ray: Ray<S, D>,
     /// The maximum amount of reflections this ray will do. If this is `Some(n)` the ray
     /// will perform at most `n` reflections.
+    ///
+    /// If this is `None`, [`SimulationParams::reflection_cap`] is used instead. `Some(0)`
+    /// means the ray doesn't reflect at all, nor is it drawn past it's origin.
     pub reflection_cap: Option<usize>,
+    /// Color of the ray's path. If this is `None`, the ray is assigned a color from
+    /// [`SimulationParams::palette`], according to it's position among the simulation's
+    /// rays, or drawn in grey if that's `None` too. Default: `None`
+    pub color: Option<palette::Rgb>,
 }
 
 impl<const D: usize, S: PartialEq> PartialEq for SimulationRay<S, D> {
     fn eq(&self, other: &Self) -> bool {
-        self.ray == other.ray && self.reflection_cap == other.reflection_cap
+        self.ray == other.ray
+            && self.reflection_cap == other.reflection_cap
+            && self.color == other.color
     }
 }
 
@@ -114,6 +157,7 @@ impl<S, const D: usize> SimulationRay<S, D> {
         Self {
             ray,
             reflection_cap: None,
+            color: None,
         }
     }
 
@@ -123,20 +167,17 @@ impl<S, const D: usize> SimulationRay<S, D> {
         Self::from_ray(Ray::new_unit_dir(origin, dir))
     }
 
-    /// Does not normalize `dir`
     #[inline]
     #[must_use]
-    pub fn new_unchecked_dir(
-        origin: impl Into<SVector<S, D>>,
-        dir: impl Into<SVector<S, D>>,
-    ) -> Self {
-        Self::from_ray(Ray::new_unchecked_dir(origin, dir))
+    pub fn with_reflection_cap(mut self, max: usize) -> Self {
+        self.reflection_cap = Some(max);
+        self
     }
 
     #[inline]
     #[must_use]
-    pub fn with_reflection_cap(mut self, max: usize) -> Self {
-        self.reflection_cap = Some(max);
+    pub fn with_color(mut self, color: palette::Rgb) -> Self {
+        self.color = Some(color);
         self
     }
 }
@@ -156,6 +197,16 @@ impl<S: ComplexField, const D: usize> SimulationRay<S, D> {
     pub fn new(origin: impl Into<SVector<S, D>>, dir: impl Into<SVector<S, D>>) -> Self {
         Self::from_ray(Ray::new(origin, dir))
     }
+
+    /// Does not normalize `dir`, see [`Ray::new_unchecked_dir`]
+    #[inline]
+    #[must_use]
+    pub fn new_unchecked_dir(
+        origin: impl Into<SVector<S, D>>,
+        dir: impl Into<SVector<S, D>>,
+    ) -> Self {
+        Self::from_ray(Ray::new_unchecked_dir(origin, dir))
+    }
 }
 
 /// A set of global parameters for a simulation.
@@ -168,6 +219,17 @@ pub struct SimulationParams<S> {
     /// Whether to detect if the ray's path ends up in an infinite loop,
     /// and halt the simulation accordingly. Default: `false`
     pub detect_loops: bool,
+    /// The maximum amount of reflections performed by rays that don't
+    /// specify one themselves, `None` for no limit. Default: `None`
+    ///
+    /// See [`resolve_reflection_cap`] for more info.
+    pub reflection_cap: Option<usize>,
+    /// How rays are prevented from intersecting again with the surface they have just been
+    /// reflected by. Default: [`ExclusionPolicy::EpsTravel`]
+    pub exclusion_policy: ExclusionPolicy,
+    /// The palette rays that don't specify a color are assigned one from, `None` to
+    /// draw them all in grey. Default: `None`
+    pub palette: Option<palette::Palette>,
 }
 
 impl<S: FloatCore + 'static> Default for SimulationParams<S>
@@ -178,49 +240,241 @@ where
         Self {
             epsilon: S::epsilon() * 64.0.as_(),
             detect_loops: false,
+            reflection_cap: None,
+            exclusion_policy: ExclusionPolicy::EpsTravel,
+            palette: None,
+        }
+    }
+}
+
+/// An error preventing a simulation from being visualized.
+#[derive(Debug)]
+pub enum SimulationError {
+    /// One of the shader programs used to render the simulation couldn't be created.
+    Program(gl::ProgramCreationError),
+    /// One of the vertex buffers used to render the simulation couldn't be created.
+    VertexBuffer(gl::vertex::BufferCreationError),
+    /// One of the index buffers used to render the simulation couldn't be created.
+    IndexBuffer(gl::index::BufferCreationError),
+    /// The event loop failed.
+    EventLoop(EventLoopError),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Program(e) => write!(f, "failed to create shader program: {e}"),
+            Self::VertexBuffer(e) => write!(f, "failed to create vertex buffer: {e}"),
+            Self::IndexBuffer(e) => write!(f, "failed to create index buffer: {e}"),
+            Self::EventLoop(e) => write!(f, "failed to run event loop: {e}"),
         }
     }
 }
 
+impl std::error::Error for SimulationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Program(e) => Some(e),
+            Self::VertexBuffer(e) => Some(e),
+            Self::IndexBuffer(e) => Some(e),
+            Self::EventLoop(e) => Some(e),
+        }
+    }
+}
+
+impl From<gl::ProgramCreationError> for SimulationError {
+    fn from(e: gl::ProgramCreationError) -> Self {
+        Self::Program(e)
+    }
+}
+
+impl From<gl::vertex::BufferCreationError> for SimulationError {
+    fn from(e: gl::vertex::BufferCreationError) -> Self {
+        Self::VertexBuffer(e)
+    }
+}
+
+impl From<gl::index::BufferCreationError> for SimulationError {
+    fn from(e: gl::index::BufferCreationError) -> Self {
+        Self::IndexBuffer(e)
+    }
+}
+
+impl From<EventLoopError> for SimulationError {
+    fn from(e: EventLoopError) -> Self {
+        Self::EventLoop(e)
+    }
+}
+
+/// An error preventing a [`SimulationWindow`] from being created.
+#[derive(Debug)]
+pub enum DisplayCreationError {
+    /// The event loop couldn't be created.
+    EventLoop(EventLoopError),
+    /// The window couldn't be created, or no OpenGL configuration
+    /// matching the requested one is available.
+    Window(Box<dyn Error>),
+    /// The window was created with a zero width or height.
+    ZeroSized,
+    /// The OpenGL surface or context couldn't be created.
+    Glutin(glutin::error::Error),
+    /// The OpenGL context created isn't supported by `glium`.
+    IncompatibleOpenGl(IncompatibleOpenGl),
+}
+
+impl fmt::Display for DisplayCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EventLoop(e) => write!(f, "failed to create event loop: {e}"),
+            Self::Window(e) => write!(f, "failed to create window: {e}"),
+            Self::ZeroSized => f.write_str("the window has a zero width or height"),
+            Self::Glutin(e) => write!(f, "failed to create OpenGL context: {e}"),
+            Self::IncompatibleOpenGl(e) => write!(f, "incompatible OpenGL context: {e}"),
+        }
+    }
+}
+
+impl Error for DisplayCreationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::EventLoop(e) => Some(e),
+            Self::Window(e) => Some(e.as_ref()),
+            Self::ZeroSized => None,
+            Self::Glutin(e) => Some(e),
+            Self::IncompatibleOpenGl(e) => Some(e),
+        }
+    }
+}
+
+impl From<EventLoopError> for DisplayCreationError {
+    fn from(e: EventLoopError) -> Self {
+        Self::EventLoop(e)
+    }
+}
+
+impl From<glutin::error::Error> for DisplayCreationError {
+    fn from(e: glutin::error::Error) -> Self {
+        Self::Glutin(e)
+    }
+}
+
+impl From<IncompatibleOpenGl> for DisplayCreationError {
+    fn from(e: IncompatibleOpenGl) -> Self {
+        Self::IncompatibleOpenGl(e)
+    }
+}
+
 /// A handle for the window used to visualize simulations.
 pub struct SimulationWindow {
-    events_loop: glutin::event_loop::EventLoop<()>,
-    display: gl::Display,
+    events_loop: event_loop::EventLoop<()>,
+    window: window::Window,
+    display: gl::Display<WindowSurface>,
 }
 
 impl SimulationWindow {
     #[inline]
     /// Create a new window to visualize simulations in from a `winit`
-    /// [`WindowBuilder`](window::WindowBuilder) and a [`glutin::ContextBuilder`].
-    pub fn new<T: glutin::ContextCurrentState>(
+    /// [`WindowBuilder`](window::WindowBuilder) and a [`glutin::config::ConfigTemplateBuilder`].
+    ///
+    /// Among the OpenGL configurations matching `cb`, the one with the most samples per
+    /// pixel is used. If `vsync` is `true`, buffer swaps are synchronized with the display's
+    /// refresh rate, when the platform supports it.
+    ///
+    /// # Panics
+    ///
+    /// If the platform reports that OpenGL configurations matching `cb` exist, but then
+    /// provides none, (`glutin_winit` gives no way of reporting this as an error).
+    pub fn new(
         wb: window::WindowBuilder,
-        cb: glutin::ContextBuilder<T>,
+        cb: ConfigTemplateBuilder,
+        vsync: bool,
     ) -> Result<Self, DisplayCreationError> {
-        let events_loop = event_loop::EventLoop::default();
-        gl::Display::new(wb, cb, &events_loop).map(|display| Self {
+        let events_loop = event_loop::EventLoop::new()?;
+
+        let (window, config) = DisplayBuilder::new()
+            .with_window_builder(Some(wb))
+            .build(&events_loop, cb, |configs| {
+                configs
+                    .reduce(|a, b| {
+                        if b.num_samples() > a.num_samples() {
+                            b
+                        } else {
+                            a
+                        }
+                    })
+                    .expect("no OpenGL configuration provided")
+            })
+            .map_err(DisplayCreationError::Window)?;
+
+        let window =
+            window.ok_or_else(|| DisplayCreationError::Window("no window created".into()))?;
+
+        let (width, height): (u32, u32) = window.inner_size().into();
+        let (Some(width), Some(height)) = (NonZeroU32::new(width), NonZeroU32::new(height)) else {
+            return Err(DisplayCreationError::ZeroSized);
+        };
+
+        let raw_window_handle = window.raw_window_handle();
+        let gl_display = config.display();
+
+        let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new().build(
+            raw_window_handle,
+            width,
+            height,
+        );
+        // SAFETY: the window outlives the surface, they are moved together into `Self`
+        let surface = unsafe { gl_display.create_window_surface(&config, &surface_attributes)? };
+
+        let context_attributes = ContextAttributesBuilder::new().build(Some(raw_window_handle));
+        // SAFETY: same as above
+        let context = unsafe { gl_display.create_context(&config, &context_attributes)? }
+            .make_current(&surface)?;
+
+        if vsync {
+            // not all platforms support setting the swap interval, run without vsync there
+            let _ = surface.set_swap_interval(&context, SwapInterval::Wait(NonZeroU32::MIN));
+        }
+
+        let display = gl::Display::from_context_surface(context, surface)?;
+
+        Ok(Self {
             events_loop,
+            window,
             display,
         })
     }
 
+    /// Runs the simulation, and visualizes it in this window, until it is closed.
+    ///
+    /// Ray origins are marked using a geometry shader. If it isn't supported, or the
+    /// `MIROIR_NO_GEOMETRY_SHADER` environment variable is set, they are drawn as
+    /// crosses built on the CPU instead.
+    ///
+    /// Fails if any of the shader programs, or vertex/index buffers (including those of the
+    /// mirrors' render data, see [`List::try_push`]) used to render the simulation can't be
+    /// created, or if the event loop fails.
     #[inline]
     pub fn run<const D: usize, M>(
         self,
         mirror: &M,
         rays: impl IntoIterator<Item = SimulationRay<M::Scalar, D>>,
         params: SimulationParams<M::Scalar>,
-    ) where
+    ) -> Result<(), SimulationError>
+    where
         M: Mirror<D, Scalar: RealField> + OpenGLRenderable + ?Sized,
         Vertex<D>: gl::Vertex + From<SVector<M::Scalar, D>>,
     {
         let Self {
             events_loop,
+            window,
             display,
         } = self;
 
-        let app = SimulationRenderData::from_simulation(mirror, rays, &display, params);
+        let app = SimulationRenderData::from_simulation(mirror, rays, &display, params)?;
+
+        app.run(mirror, window, display, events_loop)?;
 
-        app.run(display, events_loop);
+        Ok(())
     }
 }
 
@@ -231,9 +485,8 @@ impl Default for SimulationWindow {
             window::WindowBuilder::new()
                 .with_inner_size(dpi::LogicalSize::new(1280, 720))
                 .with_title("Miroir"),
-            glutin::ContextBuilder::new()
-                .with_vsync(true)
-                .with_multisampling(1 << 4),
+            ConfigTemplateBuilder::new().with_multisampling(1 << 4),
+            true,
         )
         .expect("failed to build display")
     }
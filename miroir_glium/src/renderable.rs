@@ -1,29 +1,49 @@
 use super::*;
-use core::{array, ops::AddAssign};
-use nalgebra::{RealField, Vector2};
+use core::ops::AddAssign;
+use gl::backend::Facade;
+use nalgebra::{RealField, Vector2, Vector3};
 
 /// A trait encompassing a shape that can be rendered
 ///
 /// [`Mirror`]s implementing [`OpenGLRenderable`] return objects for this trait enabling them to be rendered
 /// on-screen in simulations.
 pub trait RenderData {
-    fn vertices(&self) -> gl::vertex::VerticesSource;
-    fn indices(&self) -> gl::index::IndicesSource;
-}
-
-/// glium_shapes 3Dconvenience blanket impl
-impl RenderData for glium_shapes::sphere::Sphere {
-    fn vertices(&self) -> gl::vertex::VerticesSource {
-        self.into()
+    fn vertices(&self) -> gl::vertex::VerticesSource<'_>;
+    fn indices(&self) -> gl::index::IndicesSource<'_>;
+    /// Whether this object should be rebuilt, because it's tessellation is no longer
+    /// appropriate under `budget`. Default: `false`
+    ///
+    /// Only relevant for curved surfaces, see [`Tessellation::is_stale`].
+    #[inline]
+    fn is_stale(&self, budget: &TessellationBudget) -> bool {
+        let _ = budget;
+        false
     }
-
-    fn indices(&self) -> gl::index::IndicesSource {
-        self.into()
+    /// Rebuilds this object's buffers, tessellating it according to `budget`.
+    /// Called when [`Self::is_stale`] returns `true`. Default: does nothing
+    #[inline]
+    fn retessellate(
+        &mut self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+    ) -> Result<(), SimulationError> {
+        let _ = (display, budget);
+        Ok(())
+    }
+    /// The opposite corners of the smallest axis-aligned box containing this object,
+    /// (`z = 0` in 2D), `None` if unknown. Default: `None`
+    ///
+    /// Used to size the crosses marking ray origins when geometry shaders are unavailable.
+    #[inline]
+    fn bounds(&self) -> Option<[[f32; 3]; 2]> {
+        None
     }
 }
 
 /// A wrapper around a `Vec<T>` that only allows pushing/appending/extending etc...
-pub struct List<T>(pub(crate) Vec<T>);
+///
+/// Also records the first error passed to [`Self::try_push`].
+pub struct List<T>(pub(crate) Vec<T>, pub(crate) Option<SimulationError>);
 
 /// Most of these methods forward their implementation to the inner [`Vec`].
 /// Check the relevant documentation when needed.
@@ -63,6 +83,18 @@ impl<T> List<T> {
         self.0.push(v);
     }
 
+    /// Pushes `v` if it was successfully created, records the error otherwise, and
+    /// ignores it if one was already recorded. The simulation then fails with it, see
+    /// [`SimulationWindow::run`].
+    #[inline]
+    pub fn try_push(&mut self, v: Result<T, impl Into<SimulationError>>) {
+        match v {
+            Ok(v) => self.push(v),
+            Err(e) if self.1.is_none() => self.1 = Some(e.into()),
+            Err(_) => {}
+        }
+    }
+
     #[inline]
     pub fn append(&mut self, vec: &mut Vec<T>) {
         self.0.append(vec);
@@ -86,132 +118,446 @@ impl<T> Extend<T> for List<T> {
 
 #[impl_trait_for_tuples::impl_for_tuples(16)]
 pub trait OpenGLRenderable {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>);
+    /// Appends the objects needed to render `self` to `list`.
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>);
+
+    /// Appends the objects needed to render `self` to `list`, tessellating
+    /// curved surfaces according to `budget`.
+    ///
+    /// Default: ignores `budget`, and calls [`OpenGLRenderable::append_render_data`]
+    #[inline]
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        let _ = budget;
+        self.append_render_data(display, list);
+    }
 }
 
 impl<T: OpenGLRenderable> OpenGLRenderable for [T] {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.iter()
             .for_each(|a| a.append_render_data(display, list));
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.iter()
+            .for_each(|a| a.append_render_data_with_budget(display, budget, list));
+    }
 }
 
 impl<const N: usize, T: OpenGLRenderable> OpenGLRenderable for [T; N] {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.as_slice().append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.as_slice()
+            .append_render_data_with_budget(display, budget, list);
+    }
 }
 
 // It's clear that all these impls use the `Deref` trait, but writing a blanket impl over all
 // types implementing `Deref` makes the trait unusable downstream
 impl<T: OpenGLRenderable + ?Sized> OpenGLRenderable for Box<T> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.deref().append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.deref()
+            .append_render_data_with_budget(display, budget, list);
+    }
 }
 
 impl<T: OpenGLRenderable + ?Sized> OpenGLRenderable for Arc<T> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.deref().append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.deref()
+            .append_render_data_with_budget(display, budget, list);
+    }
 }
 
 impl<T: OpenGLRenderable + ?Sized> OpenGLRenderable for Rc<T> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.deref().append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.deref()
+            .append_render_data_with_budget(display, budget, list);
+    }
 }
 
 impl<T: OpenGLRenderable> OpenGLRenderable for Vec<T> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.deref().append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.deref()
+            .append_render_data_with_budget(display, budget, list);
+    }
 }
 
-impl<'a, T: OpenGLRenderable + ?Sized> OpenGLRenderable for &'a T {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+impl<T: OpenGLRenderable + ?Sized> OpenGLRenderable for &T {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         (*self).append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        (*self).append_render_data_with_budget(display, budget, list);
+    }
 }
 
-impl<'a, T: OpenGLRenderable + ?Sized> OpenGLRenderable for &'a mut T {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+impl<T: OpenGLRenderable + ?Sized> OpenGLRenderable for &mut T {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         self.deref().append_render_data(display, list);
     }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.deref()
+            .append_render_data_with_budget(display, budget, list);
+    }
 }
 
-// Use glium_shapes::sphere::Sphere for the 3D implementation
-impl<S: RealField + AsPrimitive<f32>> OpenGLRenderable for miroir_shapes::Sphere<S, 3> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
-        let r = self.radius().as_();
-        let [x, y, z] = self.center.map(|s| s.as_()).into();
+impl<T: OpenGLRenderable> OpenGLRenderable for Prioritized<T> {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
+        self.0.append_render_data(display, list);
+    }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        self.0.append_render_data_with_budget(display, budget, list);
+    }
+}
+
+struct SphereRenderData {
+    vertices: gl::VertexBuffer<Vertex3D>,
+    indices: gl::IndexBuffer<u32>,
+    center: [f32; 3],
+    radius: f32,
+    /// `None` if the sphere was tessellated with a fixed number of segments
+    tessellation: Option<Tessellation>,
+}
+
+impl SphereRenderData {
+    /// A UV sphere, with `rings` horizontal divisions and `sectors` vertical ones.
+    fn new(
+        center: [f32; 3],
+        radius: f32,
+        rings: u32,
+        sectors: u32,
+        tessellation: Option<Tessellation>,
+        display: &dyn Facade,
+    ) -> Result<Self, SimulationError> {
+        use core::f32::consts::{PI, TAU};
+
+        let c = Vector3::from(center);
+
+        let vertices: Vec<Vertex3D> = (0..=rings)
+            .flat_map(|i| {
+                let (sin_theta, cos_theta) = (i as f32 / rings as f32 * PI).sin_cos();
+                (0..=sectors).map(move |j| {
+                    let (sin_phi, cos_phi) = (j as f32 / sectors as f32 * TAU).sin_cos();
+                    let p = Vector3::new(sin_theta * cos_phi, cos_theta, sin_theta * sin_phi);
+                    (p * radius + c).into()
+                })
+            })
+            .collect();
+
+        let row = sectors + 1;
+
+        let indices: Vec<u32> = (0..rings)
+            .flat_map(|i| (0..sectors).map(move |j| (i, j)))
+            .flat_map(|(i, j)| {
+                let [a, b] = [i * row + j, (i + 1) * row + j];
+                [a, b, a + 1, a + 1, b, b + 1]
+            })
+            .collect();
+
+        Ok(Self {
+            vertices: gl::VertexBuffer::immutable(display, &vertices)?,
+            indices: gl::IndexBuffer::immutable(
+                display,
+                gl::index::PrimitiveType::TrianglesList,
+                &indices,
+            )?,
+            center,
+            radius,
+            tessellation,
+        })
+    }
+
+    /// A sphere tessellated according to `budget`.
+    fn tessellated(
+        center: [f32; 3],
+        radius: f32,
+        budget: &TessellationBudget,
+        display: &dyn Facade,
+    ) -> Result<Self, SimulationError> {
+        let tessellation = budget.tessellate(center, radius, radius);
+        let sectors = tessellation.segments;
+
+        Self::new(
+            center,
+            radius,
+            sectors.div_ceil(2),
+            sectors,
+            Some(tessellation),
+            display,
+        )
+    }
+}
+
+impl RenderData for SphereRenderData {
+    fn vertices(&self) -> gl::vertex::VerticesSource<'_> {
+        (&self.vertices).into()
+    }
+
+    fn indices(&self) -> gl::index::IndicesSource<'_> {
+        (&self.indices).into()
+    }
+
+    fn is_stale(&self, budget: &TessellationBudget) -> bool {
+        self.tessellation.is_some_and(|t| t.is_stale(budget))
+    }
+
+    fn retessellate(
+        &mut self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+    ) -> Result<(), SimulationError> {
+        *self = Self::tessellated(self.center, self.radius, budget, display)?;
+        Ok(())
+    }
+
+    fn bounds(&self) -> Option<[[f32; 3]; 2]> {
+        Some(ball_bounds(self.center, self.radius))
+    }
+}
+
+/// The opposite corners of the smallest axis-aligned box containing a ball.
+fn ball_bounds(center: [f32; 3], radius: f32) -> [[f32; 3]; 2] {
+    [center.map(|c| c - radius), center.map(|c| c + radius)]
+}
 
-        let sphere = gl_shapes::sphere::SphereBuilder::new()
-            .scale(r, r, r)
-            .translate(x, y, z)
-            .with_divisions(60, 60)
-            .build(display)
-            .unwrap();
+impl<S: RealField + AsPrimitive<f32>> OpenGLRenderable for miroir_shapes::Sphere<S, 3> {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
+        list.try_push(
+            SphereRenderData::new(
+                self.center.map(|s| s.as_()).into(),
+                self.radius().as_(),
+                60,
+                60,
+                None,
+                display,
+            )
+            .map(|data| Box::new(data) as _),
+        )
+    }
 
-        list.push(Box::new(sphere))
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        list.try_push(
+            SphereRenderData::tessellated(
+                self.center.map(|s| s.as_()).into(),
+                self.radius().as_(),
+                budget,
+                display,
+            )
+            .map(|data| Box::new(data) as _),
+        )
     }
 }
 
 struct Circle {
     vertices: gl::VertexBuffer<Vertex2D>,
+    center: [f32; 2],
+    radius: f32,
+    /// `None` if the circle was tessellated with a fixed number of segments
+    tessellation: Option<Tessellation>,
 }
 
 impl Circle {
-    fn new<const N: usize>(center: [f32; 2], radius: f32, display: &gl::Display) -> Self {
+    fn new(
+        center: [f32; 2],
+        radius: f32,
+        segments: u32,
+        tessellation: Option<Tessellation>,
+        display: &dyn Facade,
+    ) -> Result<Self, SimulationError> {
         let c = SVector::from(center);
 
         use core::f32::consts::TAU;
 
-        let points: [_; N] = array::from_fn(|i| {
-            let w = i as f32 / N as f32 * TAU;
-            let p = Vector2::new(w.cos(), w.sin());
-            (p * radius + c).into()
-        });
-
-        let vertices = gl::VertexBuffer::immutable(display, points.as_slice()).unwrap();
+        let points: Vec<Vertex2D> = (0..segments)
+            .map(|i| {
+                let w = i as f32 / segments as f32 * TAU;
+                let p = Vector2::new(w.cos(), w.sin());
+                (p * radius + c).into()
+            })
+            .collect();
+
+        let vertices = gl::VertexBuffer::immutable(display, points.as_slice())?;
+
+        Ok(Self {
+            vertices,
+            center,
+            radius,
+            tessellation,
+        })
+    }
 
-        Self { vertices }
+    /// A circle tessellated according to `budget`.
+    fn tessellated(
+        center: [f32; 2],
+        radius: f32,
+        budget: &TessellationBudget,
+        display: &dyn Facade,
+    ) -> Result<Self, SimulationError> {
+        let [x, y] = center;
+        let tessellation = budget.tessellate([x, y, 0.], radius, radius);
+
+        Self::new(
+            center,
+            radius,
+            tessellation.segments,
+            Some(tessellation),
+            display,
+        )
     }
 }
 
 impl RenderData for Circle {
-    fn vertices(&self) -> gl::vertex::VerticesSource {
+    fn vertices(&self) -> gl::vertex::VerticesSource<'_> {
         (&self.vertices).into()
     }
 
-    fn indices(&self) -> gl::index::IndicesSource {
+    fn indices(&self) -> gl::index::IndicesSource<'_> {
         gl::index::IndicesSource::NoIndices {
             primitives: gl::index::PrimitiveType::LineLoop,
         }
     }
+
+    fn is_stale(&self, budget: &TessellationBudget) -> bool {
+        self.tessellation.is_some_and(|t| t.is_stale(budget))
+    }
+
+    fn retessellate(
+        &mut self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+    ) -> Result<(), SimulationError> {
+        *self = Self::tessellated(self.center, self.radius, budget, display)?;
+        Ok(())
+    }
+
+    fn bounds(&self) -> Option<[[f32; 3]; 2]> {
+        let [x, y] = self.center;
+        let [[x0, y0, _], [x1, y1, _]] = ball_bounds([x, y, 0.], self.radius);
+        Some([[x0, y0, 0.], [x1, y1, 0.]])
+    }
 }
 
 // in 2D, the list of vertices of a circle is easy to calculate
 impl<S: RealField + AsPrimitive<f32>> OpenGLRenderable for miroir_shapes::Sphere<S, 2> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
-        list.push(Box::new(Circle::new::<360>(
-            self.center.map(|s| s.as_()).into(),
-            self.radius().as_(),
-            display,
-        )))
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
+        list.try_push(
+            Circle::new(
+                self.center.map(|s| s.as_()).into(),
+                self.radius().as_(),
+                360,
+                None,
+                display,
+            )
+            .map(|data| Box::new(data) as _),
+        )
+    }
+
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        list.try_push(
+            Circle::tessellated(
+                self.center.map(|s| s.as_()).into(),
+                self.radius().as_(),
+                budget,
+                display,
+            )
+            .map(|data| Box::new(data) as _),
+        )
     }
 }
 
 struct SimplexRenderData<const D: usize> {
     vertices: gl::VertexBuffer<Vertex<D>>,
+    bounds: Option<[[f32; 3]; 2]>,
 }
 
 impl<const D: usize> RenderData for SimplexRenderData<D> {
-    fn vertices(&self) -> gl::vertex::VerticesSource {
+    fn vertices(&self) -> gl::vertex::VerticesSource<'_> {
         (&self.vertices).into()
     }
 
-    fn indices(&self) -> gl::index::IndicesSource {
+    fn indices(&self) -> gl::index::IndicesSource<'_> {
         gl::index::IndicesSource::NoIndices {
             primitives: match D {
                 0 => unreachable!("dimension must not be zero"),
@@ -220,6 +566,10 @@ impl<const D: usize> RenderData for SimplexRenderData<D> {
             },
         }
     }
+
+    fn bounds(&self) -> Option<[[f32; 3]; 2]> {
+        self.bounds
+    }
 }
 
 impl<S, const D: usize> OpenGLRenderable for miroir_shapes::Simplex<S, D>
@@ -227,38 +577,39 @@ where
     Vertex<D>: gl::Vertex + From<SVector<S, D>>,
     SVector<S, D>: AddAssign + Clone,
 {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
         let vertices = self.vertices().map(Vertex::from);
 
-        list.push(Box::new(SimplexRenderData {
-            vertices: gl::VertexBuffer::new(display, vertices.as_slice()).unwrap(),
-        }))
+        let bounds = bounding_box(vertices.iter().map(Vertex::position_3d));
+
+        list.try_push(
+            gl::VertexBuffer::new(display, vertices.as_slice())
+                .map(|vertices| Box::new(SimplexRenderData { vertices, bounds }) as _),
+        )
     }
 }
 
 struct CylinderRenderData {
     vertices: gl::VertexBuffer<Vertex3D>,
+    start: Vector3<f32>,
+    dist: Vector3<f32>,
+    radius: f32,
+    /// `None` if the cylinder was tessellated with a fixed number of segments
+    tessellation: Option<Tessellation>,
 }
 
-impl RenderData for CylinderRenderData {
-    fn vertices(&self) -> gl::vertex::VerticesSource {
-        (&self.vertices).into()
-    }
-
-    fn indices(&self) -> gl::index::IndicesSource {
-        gl::index::IndicesSource::NoIndices {
-            primitives: gl::index::PrimitiveType::TriangleStrip,
-        }
-    }
-}
-
-impl<S: RealField + AsPrimitive<f32>> OpenGLRenderable for miroir_shapes::Cylinder<S> {
-    fn append_render_data(&self, display: &gl::Display, list: &mut List<Box<dyn RenderData>>) {
-        const NUM_POINTS: usize = 360;
-
-        let d = self.segment_dist().map(|v| v.as_());
-
-        let d_norm = d.normalize();
+impl CylinderRenderData {
+    /// A cylinder whose axis goes from `start` to `start + dist`, with
+    /// it's circular cross-sections divided into `segments` segments.
+    fn new(
+        start: Vector3<f32>,
+        dist: Vector3<f32>,
+        radius: f32,
+        segments: u32,
+        tessellation: Option<Tessellation>,
+        display: &dyn Facade,
+    ) -> Result<Self, SimulationError> {
+        let d_norm = dist.normalize();
 
         let v = nalgebra::SVector::from([0., 0., 1.]) + d_norm;
 
@@ -272,26 +623,122 @@ impl<S: RealField + AsPrimitive<f32>> OpenGLRenderable for miroir_shapes::Cylind
         let o = nalgebra::SMatrix::from_fn(|i, j| v[i] * v[j]);
         let rot = 2.0 / v.norm_squared() * o - id;
 
-        let r = self.radius().as_();
-        let start = self.start().map(|s| s.as_());
-
         use core::f32::consts::TAU;
 
-        const NUM_VERTICES: usize = (NUM_POINTS + 1) * 2;
+        let num_points = segments as usize;
 
-        let mut vertices: [_; NUM_VERTICES] = [Default::default(); NUM_VERTICES];
+        let mut vertices = vec![Vertex3D::default(); (num_points + 1) * 2];
 
         vertices.chunks_exact_mut(2).enumerate().for_each(|(i, w)| {
             let [a, b] = w else { unreachable!() };
 
-            let [x, y]: [f32; 2] = (i as f32 / NUM_POINTS as f32 * TAU).sin_cos().into();
-            let vertex = [x * r, y * r, 0.];
+            let [x, y]: [f32; 2] = (i as f32 / num_points as f32 * TAU).sin_cos().into();
+            let vertex = [x * radius, y * radius, 0.];
             let k = rot * nalgebra::SVector::from(vertex) + start;
-            (*a, *b) = (k.into(), (k + d).into())
+            (*a, *b) = (k.into(), (k + dist).into())
         });
 
-        let vertices = gl::VertexBuffer::immutable(display, vertices.as_slice()).unwrap();
+        let vertices = gl::VertexBuffer::immutable(display, vertices.as_slice())?;
+
+        Ok(Self {
+            vertices,
+            start,
+            dist,
+            radius,
+            tessellation,
+        })
+    }
+
+    /// A cylinder tessellated according to `budget`.
+    fn tessellated(
+        start: Vector3<f32>,
+        dist: Vector3<f32>,
+        radius: f32,
+        budget: &TessellationBudget,
+        display: &dyn Facade,
+    ) -> Result<Self, SimulationError> {
+        // the cylinder lies in the ball centered at it's center, containing it's end caps
+        let half_length = dist.norm() / 2.;
+        let extent = (half_length * half_length + radius * radius).sqrt();
+        let tessellation = budget.tessellate(start + dist / 2., extent, radius);
+
+        Self::new(
+            start,
+            dist,
+            radius,
+            tessellation.segments,
+            Some(tessellation),
+            display,
+        )
+    }
+}
+
+impl RenderData for CylinderRenderData {
+    fn vertices(&self) -> gl::vertex::VerticesSource<'_> {
+        (&self.vertices).into()
+    }
+
+    fn indices(&self) -> gl::index::IndicesSource<'_> {
+        gl::index::IndicesSource::NoIndices {
+            primitives: gl::index::PrimitiveType::TriangleStrip,
+        }
+    }
+
+    fn is_stale(&self, budget: &TessellationBudget) -> bool {
+        self.tessellation.is_some_and(|t| t.is_stale(budget))
+    }
+
+    fn retessellate(
+        &mut self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+    ) -> Result<(), SimulationError> {
+        *self = Self::tessellated(self.start, self.dist, self.radius, budget, display)?;
+        Ok(())
+    }
+
+    fn bounds(&self) -> Option<[[f32; 3]; 2]> {
+        // the cylinder lies in the union of the balls containing it's end caps' boundaries
+        let [start_min, start_max] = ball_bounds(self.start.into(), self.radius);
+        let [end_min, end_max] = ball_bounds((self.start + self.dist).into(), self.radius);
+
+        Some([
+            array::from_fn(|i| start_min[i].min(end_min[i])),
+            array::from_fn(|i| start_max[i].max(end_max[i])),
+        ])
+    }
+}
+
+impl<S: RealField + AsPrimitive<f32>> OpenGLRenderable for miroir_shapes::Cylinder<S> {
+    fn append_render_data(&self, display: &dyn Facade, list: &mut List<Box<dyn RenderData>>) {
+        list.try_push(
+            CylinderRenderData::new(
+                self.start().map(|s| s.as_()),
+                self.segment_dist().map(|v| v.as_()),
+                self.radius().as_(),
+                360,
+                None,
+                display,
+            )
+            .map(|data| Box::new(data) as _),
+        )
+    }
 
-        list.push(Box::new(CylinderRenderData { vertices }))
+    fn append_render_data_with_budget(
+        &self,
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+        list: &mut List<Box<dyn RenderData>>,
+    ) {
+        list.try_push(
+            CylinderRenderData::tessellated(
+                self.start().map(|s| s.as_()),
+                self.segment_dist().map(|v| v.as_()),
+                self.radius().as_(),
+                budget,
+                display,
+            )
+            .map(|data| Box::new(data) as _),
+        )
     }
 }
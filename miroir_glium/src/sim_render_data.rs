@@ -1,19 +1,152 @@
-use core::f32::consts::{FRAC_PI_2, PI};
+use core::{
+    f32::consts::{FRAC_PI_2, PI},
+    mem,
+};
 
 use super::*;
 
 use camera::{Camera, CameraController};
 
-use gl::index::{NoIndices, PrimitiveType};
+use gl::{
+    backend::Facade,
+    index::{NoIndices, PrimitiveType},
+};
 use nalgebra::{Perspective3, Point3};
 const LINE_STRIP: NoIndices = NoIndices(PrimitiveType::LineStrip);
 
+// color of the paths of rays that don't specify one, when no palette is used
+const RAY_NON_LOOP_COL: [f32; 4] = [0.7, 0.7, 0.7, 0.9];
+
+/// How the rays of a simulation ended, logged once they are all traced.
+#[cfg(feature = "log")]
+#[derive(Default)]
+struct TraceSummary {
+    rays: usize,
+    reflections: usize,
+    escaped: usize,
+    looping: usize,
+    capped: usize,
+    /// Rays that don't reflect, only intersecting with the mirror behind their origin.
+    backward_only: usize,
+    /// Intersections discarded for being at NaN distances, see [`PolicyRayPath::nan_discarded`].
+    nan_discarded: usize,
+}
+
+#[cfg(feature = "log")]
+impl TraceSummary {
+    fn record(&mut self, outcome: Option<Option<usize>>, reflections: usize) {
+        self.rays += 1;
+        self.reflections += reflections;
+
+        match outcome {
+            Some(None) => self.escaped += 1,
+            Some(Some(_)) => self.looping += 1,
+            None => self.capped += 1,
+        }
+    }
+
+    fn log(&self, elapsed: time::Duration) {
+        let Self {
+            rays,
+            reflections,
+            escaped,
+            looping,
+            capped,
+            backward_only,
+            nan_discarded,
+        } = self;
+
+        log::debug!(
+            "traced {rays} rays in {elapsed:?}: {reflections} reflections, {escaped} escaped, \
+            {looping} looping, {capped} reached their reflection cap",
+        );
+
+        if *backward_only > 0 {
+            log::warn!("{backward_only} rays point away from the whole mirror");
+        }
+
+        if *nan_discarded > 0 {
+            log::warn!(
+                "discarded {nan_discarded} intersections at NaN distances, \
+                a mirror may be degenerate"
+            );
+        }
+    }
+}
+
+// length, and number of dashes, of the stub drawn behind rays pointing away from the mirror
+const BACKWARD_STUB_LENGTH: f32 = 0.5;
+const BACKWARD_STUB_DASHES: usize = 5;
+
+/// Returns the endpoints of `dashes` evenly spaced dashes along
+/// the line segment of length `length`, starting at `start`, in the direction `dir`.
+fn dashed_line<const D: usize>(
+    start: Vertex<D>,
+    dir: Vertex<D>,
+    length: f32,
+    dashes: usize,
+) -> impl Iterator<Item = Vertex<D>> {
+    // dashes and gaps have the same length
+    let step = length / (2 * dashes) as f32;
+
+    (0..2 * dashes).map(move |i| start + (i as f32 * step) * dir)
+}
+
+/// Environment variable forcing ray origins to be drawn without a geometry shader, when set.
+const NO_GEOMETRY_SHADER_VAR: &str = "MIROIR_NO_GEOMETRY_SHADER";
+
+// size of the crosses marking ray origins when geometry shaders are unavailable,
+// relative to the size of the scene
+const CROSS_RELATIVE_SIZE: f32 = 0.01;
+// size of these crosses when the scene has no size, (e.g. a single ray, reflecting nowhere)
+const CROSS_DEFAULT_SIZE: f32 = 0.025;
+
+/// Returns the endpoints of two lines crossing diagonally at `origin`, in the `xy` plane,
+/// each extending `size` units horizontally and vertically from it, in both directions.
+fn cross<const D: usize>(origin: Vertex<D>, size: f32) -> [Vertex<D>; 4] {
+    let mut t1 = Vertex::default();
+    t1.position[0] = size;
+    t1.position[1] = size;
+
+    let mut t2 = t1;
+    t2.position[1] = -t2.position[1];
+
+    [
+        origin + t1,
+        origin + -1. * t1,
+        origin + t2,
+        origin + -1. * t2,
+    ]
+}
+
+/// Returns the length of the diagonal of the bounding box of `points`, zero if there are none.
+fn scene_size(points: impl IntoIterator<Item = [f32; 3]>) -> f32 {
+    bounding_box(points).map_or(0., |[min, max]| {
+        (0..3)
+            .map(|i| max[i] - min[i])
+            .map(|d| d * d)
+            .sum::<f32>()
+            .sqrt()
+    })
+}
+
 pub struct SimulationRenderData<const D: usize> {
+    /// Single points, expanded by `starting_pts_program` if present,
+    /// pairs of points forming crosses otherwise, see `Self::build_crosses`.
     ray_origins: gl::VertexBuffer<Vertex<D>>,
-    ray_paths: Vec<(gl::VertexBuffer<Vertex<D>>, gl::VertexBuffer<Vertex<D>>)>,
+    /// The ray origins, and the points of the rays' paths, kept until the
+    /// crosses are built, if `starting_pts_program` is absent.
+    cross_points: (Vec<Vertex<D>>, Vec<[f32; 3]>),
+    /// The part of each ray's path that isn't a loop, the loop, and the color of the former.
+    ray_paths: Vec<(
+        gl::VertexBuffer<Vertex<D>>,
+        gl::VertexBuffer<Vertex<D>>,
+        [f32; 4],
+    )>,
+    backward_stubs: gl::VertexBuffer<Vertex<D>>,
     mirrors: Vec<Box<dyn RenderData>>,
     program: gl::Program,
-    starting_pts_program: gl::Program,
+    starting_pts_program: Option<gl::Program>,
 }
 
 const FRAGMENT_SHADER_SRC: &str = r"
@@ -101,11 +234,11 @@ where
     pub(crate) fn from_simulation<M, R>(
         mirror: &M,
         rays: R,
-        display: &gl::Display,
+        display: &dyn Facade,
         params: SimulationParams<M::Scalar>,
-    ) -> Self
+    ) -> Result<Self, SimulationError>
     where
-        M: Mirror<D, Scalar: RealField> + OpenGLRenderable + ?Sized,
+        M: Mirror<D, Scalar: RealField> + ?Sized,
         R: IntoIterator<Item = SimulationRay<M::Scalar, D>>,
         Vertex<D>: From<SVector<M::Scalar, D>>,
     {
@@ -118,48 +251,78 @@ where
         };
 
         let program =
-            gl::Program::from_source(display, vertex_shader_src, FRAGMENT_SHADER_SRC, None)
-                .unwrap();
-
-        let starting_pts_program = gl::Program::from_source(
-            display,
-            vertex_shader_src,
-            FRAGMENT_SHADER_SRC,
-            Some(STARTING_POINT_GEOMETRY_SHADER_SRC),
-        )
-        .unwrap();
+            gl::Program::from_source(display, vertex_shader_src, FRAGMENT_SHADER_SRC, None)?;
+
+        let use_geometry_shader = std::env::var_os(NO_GEOMETRY_SHADER_VAR).is_none()
+            && gl::program::is_geometry_shader_supported(&**display.get_context());
+
+        // Some drivers claim to support geometry shaders, but not the
+        // GLSL version ours is written in, fall back to crosses then too
+        let starting_pts_program = use_geometry_shader
+            .then(|| {
+                gl::Program::from_source(
+                    display,
+                    vertex_shader_src,
+                    FRAGMENT_SHADER_SRC,
+                    Some(STARTING_POINT_GEOMETRY_SHADER_SRC),
+                )
+                .ok()
+            })
+            .flatten();
+
+        #[cfg(feature = "log")]
+        if starting_pts_program.is_none() {
+            if use_geometry_shader {
+                log::warn!(
+                    "failed to create the geometry shader program, drawing ray origins as crosses"
+                );
+            } else {
+                log::info!("geometry shaders unavailable, drawing ray origins as crosses");
+            }
+        }
 
-        let mut mirrors = List(vec![]);
+        #[cfg(feature = "log")]
+        let (start, mut summary) = (time::Instant::now(), TraceSummary::default());
 
-        mirror.append_render_data(display, &mut mirrors);
+        let mut scene_points = vec![];
 
         let mut vertex_scratch = vec![];
         let mut pt_scratch = vec![];
 
-        let mut mirrors = mirrors.into_inner();
         let mut ray_origins = vec![];
         let mut ray_paths = vec![];
+        let mut backward_stubs = vec![];
 
-        for SimulationRay {
-            ray,
-            reflection_cap,
-        } in rays
+        for (
+            i,
+            SimulationRay {
+                ray,
+                reflection_cap,
+                color,
+            },
+        ) in rays.into_iter().enumerate()
         {
+            let path_color = color
+                .or_else(|| params.palette.map(|palette| palette.color(i)))
+                .map_or(RAY_NON_LOOP_COL, |rgb| {
+                    let [r, g, b] = rgb.map(|c| f32::from(c) / 255.);
+                    [r, g, b, RAY_NON_LOOP_COL[3]]
+                });
+
             ray_origins.push(Vertex::from(ray.origin.clone()));
 
             vertex_scratch.clear();
             pt_scratch.push(ray.origin.clone());
 
-            let mut path = RayPath {
-                mirror,
-                ray,
-                eps: params.epsilon.clone(),
-            };
+            let mut path = RayPath::new(ray, mirror, params.epsilon.clone())
+                .with_policy(params.exclusion_policy);
 
             let path_iter = path.by_ref();
 
+            let cap = resolve_reflection_cap(reflection_cap, params.reflection_cap, None);
+
             let outcome = 'block: {
-                if let Some(n) = reflection_cap {
+                if let Some(n) = cap {
                     for Ray { origin, .. } in path_iter.take(n) {
                         let out = loop_index(&pt_scratch, &origin, &params.epsilon);
                         if out.is_some() {
@@ -184,11 +347,26 @@ where
                 }
             };
 
+            #[cfg(feature = "log")]
+            {
+                summary.record(outcome, pt_scratch.len() - 1);
+                summary.nan_discarded += path.nan_discarded();
+            }
+
+            if starting_pts_program.is_none() {
+                scene_points.extend(
+                    pt_scratch
+                        .iter()
+                        .cloned()
+                        .map(|p| Vertex::<D>::from(p).position_3d()),
+                );
+            }
+
             let loop_path = if let Some(Some(loop_index)) = outcome {
                 vertex_scratch.extend(pt_scratch.drain(loop_index..).map(Vertex::from));
-                gl::VertexBuffer::immutable(display, &vertex_scratch).unwrap()
+                gl::VertexBuffer::immutable(display, &vertex_scratch)?
             } else {
-                gl::VertexBuffer::empty_immutable(display, 0).unwrap()
+                gl::VertexBuffer::empty_immutable(display, 0)?
             };
 
             vertex_scratch.clear();
@@ -196,28 +374,123 @@ where
 
             if let Some(None) = outcome {
                 let last = *vertex_scratch.last().unwrap();
-                let dir = Vertex::from(path.ray.dir.clone().into_inner());
+                let dir = Vertex::from(path.ray().dir.clone().into_inner());
                 vertex_scratch.push(last + 20000. * dir);
+
+                // The ray didn't reflect at all, if that's because the mirror is entirely
+                // behind it, hint at that with a short dashed line, pointing backwards
+                if vertex_scratch.len() == 2 {
+                    let (_, behind) = path
+                        .ray()
+                        .closest_intersection_counting_behind(mirror, params.epsilon.clone());
+
+                    if behind > 0 {
+                        #[cfg(feature = "log")]
+                        {
+                            summary.backward_only += 1;
+                        }
+
+                        backward_stubs.extend(dashed_line(
+                            last,
+                            -1. * dir,
+                            BACKWARD_STUB_LENGTH,
+                            BACKWARD_STUB_DASHES,
+                        ));
+                    }
+                }
             }
 
-            let non_loop_path = gl::VertexBuffer::immutable(display, &vertex_scratch).unwrap();
+            let non_loop_path = gl::VertexBuffer::immutable(display, &vertex_scratch)?;
 
-            ray_paths.push((non_loop_path, loop_path));
+            ray_paths.push((non_loop_path, loop_path, path_color));
         }
 
-        mirrors.shrink_to_fit();
         ray_paths.shrink_to_fit();
 
-        Self {
-            ray_origins: gl::VertexBuffer::immutable(display, &ray_origins).unwrap(),
+        #[cfg(feature = "log")]
+        summary.log(start.elapsed());
+
+        // crosses are built once the mirrors' bounds are known, see `Self::build_crosses`
+        let (ray_origins, cross_points) = if starting_pts_program.is_some() {
+            (
+                gl::VertexBuffer::immutable(display, &ray_origins)?,
+                (vec![], vec![]),
+            )
+        } else {
+            (
+                gl::VertexBuffer::empty_immutable(display, 0)?,
+                (ray_origins, scene_points),
+            )
+        };
+
+        Ok(Self {
+            ray_origins,
+            cross_points,
             ray_paths,
-            mirrors,
+            backward_stubs: gl::VertexBuffer::immutable(display, &backward_stubs)?,
+            // built once the view is known, see `Self::build_mirrors`
+            mirrors: vec![],
             program,
             starting_pts_program,
+        })
+    }
+
+    /// Builds the crosses marking ray origins, sized relative to the scene, made
+    /// of the rays' paths and the mirrors, if `starting_pts_program` is absent.
+    fn build_crosses(&mut self, display: &dyn Facade) -> Result<(), SimulationError> {
+        if self.starting_pts_program.is_some() {
+            return Ok(());
+        }
+
+        let (origins, scene_points) = mem::take(&mut self.cross_points);
+
+        let mirror_points = self.mirrors.iter().filter_map(|r| r.bounds()).flatten();
+
+        let size = scene_size(scene_points.into_iter().chain(mirror_points));
+        let size = if size > 0. {
+            size * CROSS_RELATIVE_SIZE
+        } else {
+            CROSS_DEFAULT_SIZE
+        };
+
+        let crosses: Vec<_> = origins
+            .into_iter()
+            .flat_map(|origin| cross(origin, size))
+            .collect();
+
+        self.ray_origins = gl::VertexBuffer::immutable(display, &crosses)?;
+
+        Ok(())
+    }
+
+    /// Builds the mirrors' render data, tessellating curved surfaces according to `budget`.
+    fn build_mirrors(
+        &mut self,
+        mirror: &(impl OpenGLRenderable + ?Sized),
+        display: &dyn Facade,
+        budget: &TessellationBudget,
+    ) -> Result<(), SimulationError> {
+        let mut mirrors = List(vec![], None);
+
+        mirror.append_render_data_with_budget(display, budget, &mut mirrors);
+
+        if let Some(e) = mirrors.1 {
+            return Err(e);
         }
+
+        self.mirrors = mirrors.into_inner();
+        self.mirrors.shrink_to_fit();
+
+        Ok(())
     }
 
-    pub(crate) fn run(self, display: gl::Display, events_loop: glutin::event_loop::EventLoop<()>) {
+    pub(crate) fn run(
+        mut self,
+        mirror: &(impl OpenGLRenderable + ?Sized),
+        window: window::Window,
+        display: gl::Display<WindowSurface>,
+        events_loop: event_loop::EventLoop<()>,
+    ) -> Result<(), SimulationError> {
         const DEFAULT_CAMERA_POS: Point3<f32> = Point3::new(0., 0., 0.);
         const DEFAULT_CAMERA_YAW: f32 = -FRAC_PI_2;
         const DEFAULT_CAMERA_PITCH: f32 = 0.;
@@ -227,11 +500,11 @@ where
         const NEAR_PLANE: f32 = 0.001;
         const FAR_PLANE: f32 = 1000.;
 
-        use glutin::{dpi, event, event_loop, window};
+        use winit::{event, keyboard::PhysicalKey};
 
         let mut camera = Camera::new(DEFAULT_CAMERA_POS, DEFAULT_CAMERA_YAW, DEFAULT_CAMERA_PITCH);
 
-        let dpi::PhysicalSize { width, height } = display.gl_window().window().inner_size();
+        let dpi::PhysicalSize { width, height } = window.inner_size();
 
         let mut projection = Perspective3::new(
             width as f32 / height as f32,
@@ -244,10 +517,23 @@ where
 
         let mut last_render_time = std::time::Instant::now();
         let mut mouse_pressed = false;
+        let mut cursor_locked = false;
+        let mut scale_factor = window.scale_factor();
 
-        events_loop.run(move |ev, _, control_flow| match ev {
+        let budget_for = |camera: &Camera, height: u32| {
+            TessellationBudget::new(*camera.position(), DEFAULT_PROJECTION_FOV, height as f32)
+        };
+
+        self.build_mirrors(mirror, &display, &budget_for(&camera, height))?;
+        self.build_crosses(&display)?;
+
+        // errors happening while the event loop runs, returned once it exits
+        let mut error = None;
+        let error_slot = &mut error;
+
+        events_loop.run(move |ev, window_target| match ev {
             event::Event::WindowEvent { event, .. } => match event {
-                event::WindowEvent::CloseRequested => *control_flow = event_loop::ControlFlow::Exit,
+                event::WindowEvent::CloseRequested => window_target.exit(),
 
                 event::WindowEvent::Resized(physical_size) => {
                     if physical_size.width > 0 && physical_size.height > 0 {
@@ -255,83 +541,112 @@ where
                             .set_aspect(physical_size.width as f32 / physical_size.height as f32);
                     }
 
-                    display.gl_window().resize(physical_size);
+                    display.resize(physical_size.into());
                 }
 
-                event::WindowEvent::KeyboardInput { input, .. } => {
-                    if let Some(keycode) = input.virtual_keycode {
-                        camera_controller.process_keyboard(keycode, input.state);
+                event::WindowEvent::ScaleFactorChanged {
+                    scale_factor: new_scale_factor,
+                    ..
+                } => {
+                    scale_factor = new_scale_factor;
+
+                    let dpi::PhysicalSize { width, height } = window.inner_size();
+                    if width > 0 && height > 0 {
+                        projection.set_aspect(width as f32 / height as f32);
                     }
                 }
 
-                event::WindowEvent::MouseInput { button, state, .. } => {
-                    if button == event::MouseButton::Left {
-                        match state {
-                            event::ElementState::Pressed => {
-                                mouse_pressed = true;
-                                display
-                                    .gl_window()
-                                    .window()
-                                    .set_cursor_grab(window::CursorGrabMode::Locked)
-                                    .or_else(|_| {
-                                        display
-                                            .gl_window()
-                                            .window()
-                                            .set_cursor_grab(window::CursorGrabMode::Confined)
-                                    })
-                                    .unwrap();
-
-                                display.gl_window().window().set_cursor_visible(false);
+                event::WindowEvent::KeyboardInput { event, .. } => {
+                    if let PhysicalKey::Code(keycode) = event.physical_key {
+                        camera_controller.process_keyboard(keycode, event.state);
+                    }
+                }
+
+                event::WindowEvent::MouseInput {
+                    button: event::MouseButton::Left,
+                    state,
+                    ..
+                } => {
+                    match state {
+                        event::ElementState::Pressed => {
+                            mouse_pressed = true;
+
+                            // A locked cursor reports relative motion without needing to
+                            // be recentered, fall back to confining it where unsupported
+                            cursor_locked = window
+                                .set_cursor_grab(window::CursorGrabMode::Locked)
+                                .is_ok();
+
+                            if !cursor_locked {
+                                let _ = window.set_cursor_grab(window::CursorGrabMode::Confined);
                             }
 
-                            event::ElementState::Released => {
-                                mouse_pressed = false;
-                                display
-                                    .gl_window()
-                                    .window()
-                                    .set_cursor_grab(window::CursorGrabMode::None)
-                                    .unwrap();
-                                display.gl_window().window().set_cursor_visible(true);
+                            window.set_cursor_visible(false);
+                        }
+
+                        event::ElementState::Released => {
+                            mouse_pressed = false;
+                            let _ = window.set_cursor_grab(window::CursorGrabMode::None);
+                            window.set_cursor_visible(true);
+                        }
+                    }
+                }
+
+                event::WindowEvent::RedrawRequested => {
+                    let now = time::Instant::now();
+                    let dt = now - last_render_time;
+                    last_render_time = now;
+
+                    camera_controller.update_camera(&mut camera, dt);
+
+                    let budget = budget_for(&camera, window.inner_size().height);
+                    for render_data in &mut self.mirrors {
+                        if render_data.is_stale(&budget) {
+                            if let Err(e) = render_data.retessellate(&display, &budget) {
+                                *error_slot = Some(e);
+                                window_target.exit();
+                                return;
                             }
                         }
                     }
+
+                    self.render_3d(&display, &camera, &projection);
                 }
                 _ => {}
             },
-            event::Event::RedrawRequested(_) => {
-                let now = time::Instant::now();
-                let dt = now - last_render_time;
-                last_render_time = now;
-
-                camera_controller.update_camera(&mut camera, dt);
-                self.render_3d(&display, &camera, &projection);
-            }
-            event::Event::MainEventsCleared => display.gl_window().window().request_redraw(),
+            event::Event::AboutToWait => window.request_redraw(),
             event::Event::DeviceEvent {
-                event: event::DeviceEvent::MouseMotion { delta, .. },
+                event: event::DeviceEvent::MouseMotion { delta },
                 ..
-            } => {
-                if mouse_pressed {
-                    let inner_window_size = display.gl_window().window().inner_size();
-
-                    display
-                        .gl_window()
-                        .window()
-                        .set_cursor_position(dpi::PhysicalPosition {
-                            x: inner_window_size.width / 2,
-                            y: inner_window_size.height / 2,
-                        })
-                        .unwrap();
-                    camera_controller.set_mouse_delta(delta.0, delta.1)
+            } if mouse_pressed => {
+                if !cursor_locked {
+                    let dpi::PhysicalSize { width, height } = window.inner_size();
+
+                    // unsupported on some platforms (e.g. Wayland),
+                    // where the cursor can always be locked anyway
+                    let _ = window.set_cursor_position(dpi::PhysicalPosition {
+                        x: width / 2,
+                        y: height / 2,
+                    });
                 }
+
+                camera_controller
+                    .add_mouse_delta(camera::normalize_mouse_delta(delta, scale_factor));
             }
             _ => (),
-        });
+        })?;
+
+        error.map_or(Ok(()), Err)
     }
 
-    fn render_3d(&self, display: &gl::Display, camera: &Camera, projection: &Perspective3<f32>) {
+    fn render_3d(
+        &self,
+        display: &gl::Display<WindowSurface>,
+        camera: &Camera,
+        projection: &Perspective3<f32>,
+    ) {
         const RAY_LOOP_COL: [f32; 4] = [0.9, 0.2, 0.9, 1.0];
-        const RAY_NON_LOOP_COL: [f32; 4] = [0.7, 0.7, 0.7, 0.9];
+        const BACKWARD_STUB_COL: [f32; 4] = [0.9, 0.5, 0.2, 0.9];
         let mirror_color = if D == 3 {
             [0.05f32, 0.2, 0.2, 0.4]
         } else if D == 2 {
@@ -343,9 +658,10 @@ where
         let mut target = display.draw();
 
         use gl::Surface;
-        target.clear_color_and_depth((0.01, 0.01, 0.05, 1.), 1.0);
+        let [r, g, b] = palette::BACKGROUND.map(|c| f32::from(c) / 255.);
+        target.clear_color_and_depth((r, g, b, 1.), 1.0);
 
-        let perspective: [[_; 4]; 4] = projection.as_matrix().clone().into();
+        let perspective: [[_; 4]; 4] = (*projection.as_matrix()).into();
         let view: [[_; 4]; 4] = camera.calc_matrix().into();
 
         let aspect = projection.aspect();
@@ -355,7 +671,7 @@ where
             ..Default::default()
         };
 
-        for (non_loop_path, loop_path) in &self.ray_paths {
+        for (non_loop_path, loop_path, path_color) in &self.ray_paths {
             target
                 .draw(
                     non_loop_path,
@@ -364,7 +680,7 @@ where
                     &gl::uniform! {
                         perspective: perspective,
                         view: view,
-                        color_vec: RAY_NON_LOOP_COL,
+                        color_vec: *path_color,
                     },
                     &params,
                 )
@@ -385,6 +701,20 @@ where
                 .unwrap();
         }
 
+        target
+            .draw(
+                &self.backward_stubs,
+                NoIndices(PrimitiveType::LinesList),
+                &self.program,
+                &gl::uniform! {
+                    perspective: perspective,
+                    view: view,
+                    color_vec: BACKWARD_STUB_COL,
+                },
+                &params,
+            )
+            .unwrap();
+
         for render_data in self.mirrors.iter().map(Box::as_ref) {
             target
                 .draw(
@@ -401,24 +731,108 @@ where
                 .unwrap();
         }
 
-        target
-            .draw(
-                &self.ray_origins,
-                NoIndices(PrimitiveType::Points),
-                &self.starting_pts_program,
-                &gl::uniform! {
-                    perspective: perspective,
-                    view: view,
-                    // red
-                    color_vec: [1.0f32, 0.0, 0.0, 1.0],
-                    aspect: aspect,
-                },
-                &params,
-            )
-            .unwrap();
+        // red
+        let origin_color = [1.0f32, 0.0, 0.0, 1.0];
+
+        if let Some(starting_pts_program) = &self.starting_pts_program {
+            target
+                .draw(
+                    &self.ray_origins,
+                    NoIndices(PrimitiveType::Points),
+                    starting_pts_program,
+                    &gl::uniform! {
+                        perspective: perspective,
+                        view: view,
+                        color_vec: origin_color,
+                        aspect: aspect,
+                    },
+                    &params,
+                )
+                .unwrap();
+        } else {
+            target
+                .draw(
+                    &self.ray_origins,
+                    NoIndices(PrimitiveType::LinesList),
+                    &self.program,
+                    &gl::uniform! {
+                        perspective: perspective,
+                        view: view,
+                        color_vec: origin_color,
+                    },
+                    &params,
+                )
+                .unwrap();
+        }
 
         target.finish().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crosses_are_centered_on_their_origin() {
+        let origin = Vertex::from(SVector::from([1f32, 2.]));
+
+        let [a, b, c, d] = cross(origin, 0.5).map(|v| v.position);
+
+        assert_eq!([a, b], [[1.5, 2.5], [0.5, 1.5]]);
+        assert_eq!([c, d], [[1.5, 1.5], [0.5, 2.5]]);
+    }
+
+    #[test]
+    fn crosses_lie_in_the_xy_plane() {
+        let origin = Vertex::from(SVector::from([0f32, 0., 3.]));
+
+        for v in cross(origin, 1.) {
+            assert_eq!(v.position[2], 3.);
+        }
+    }
+
+    #[test]
+    fn empty_scenes_have_no_size() {
+        assert_eq!(bounding_box([]), None);
+        assert_eq!(scene_size([]), 0.);
+        assert_eq!(scene_size([[1., 2., 3.]]), 0.);
+    }
+
+    #[test]
+    fn scene_size_is_the_bounding_box_diagonal() {
+        let points = [[0., 0., 0.], [3., -1., 0.], [1., 3., 12.], [2., 0., 1.]];
+
+        assert_eq!(bounding_box(points), Some([[0., -1., 0.], [3., 3., 12.]]));
+        assert_eq!(scene_size(points), 13.);
+    }
+
+    #[test]
+    fn scene_size_in_2d() {
+        let points = [[-1f32, 0.], [2., 4.]].map(|p| Vertex::from(SVector::from(p)).position_3d());
+
+        assert_eq!(points, [[-1., 0., 0.], [2., 4., 0.]]);
+        assert_eq!(scene_size(points), 5.);
+    }
 
-        display.gl_window().window().request_redraw();
+    #[test]
+    #[cfg(feature = "log")]
+    fn outcomes_are_summarized() {
+        let mut summary = TraceSummary::default();
+        summary.record(Some(None), 3);
+        summary.record(Some(Some(1)), 7);
+        summary.record(None, 10);
+
+        let TraceSummary {
+            rays,
+            reflections,
+            escaped,
+            looping,
+            capped,
+            ..
+        } = summary;
+
+        assert_eq!([rays, reflections], [3, 20]);
+        assert_eq!([escaped, looping, capped], [1, 1, 1]);
     }
 }
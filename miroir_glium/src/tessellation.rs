@@ -0,0 +1,222 @@
+use core::f32::consts::PI;
+use miroir::nalgebra::{self, Point3};
+
+/// Returns the apparent radius, in pixels, of a circle of radius `radius`, at distance
+/// `distance` from a perspective camera with a vertical field of view of `fov` radians,
+/// rendering to a viewport `viewport_height` pixels tall.
+#[inline]
+#[must_use]
+pub fn projected_radius_px(radius: f32, distance: f32, fov: f32, viewport_height: f32) -> f32 {
+    radius * viewport_height / (2. * distance * (fov / 2.).tan())
+}
+
+/// Returns the minimum number of segments a regular polygon, inscribed in a circle of radius
+/// `radius_px`, must have, for the distance between its edges and the circle (the sagitta of
+/// each chord) to be at most `tolerance_px`.
+///
+/// Never returns less than `3`. Saturates to [`u32::MAX`] if `tolerance_px` is zero, or
+/// `radius_px` is infinite.
+///
+/// ```
+/// use miroir_glium::segments_for_chord_error;
+///
+/// // a square's edges are 1 - cos(π/4) ≈ 0.29 radii away from it's circumcircle
+/// assert_eq!(segments_for_chord_error(1., 0.3), 4);
+/// assert_eq!(segments_for_chord_error(1., 0.29), 5);
+/// // doubling the radius requires √2 times more segments
+/// assert_eq!(segments_for_chord_error(100., 0.5), 32);
+/// assert_eq!(segments_for_chord_error(200., 0.5), 45);
+/// // tiny circles are triangles
+/// assert_eq!(segments_for_chord_error(0.1, 0.5), 3);
+/// ```
+#[inline]
+#[must_use]
+pub fn segments_for_chord_error(radius_px: f32, tolerance_px: f32) -> u32 {
+    // the sagitta of a chord spanning an angle of 2π/n is r * (1 - cos(π/n))
+    let half_angle = (1. - tolerance_px / radius_px).max(-1.).acos();
+    ((PI / half_angle).ceil() as u32).max(3)
+}
+
+/// Describes how finely [`OpenGLRenderable`](crate::OpenGLRenderable)s should tessellate
+/// curved surfaces, given how they are viewed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TessellationBudget {
+    /// The position of the camera.
+    pub camera: Point3<f32>,
+    /// The camera's vertical field of view, in radians.
+    pub fov: f32,
+    /// The height of the viewport, in pixels.
+    pub viewport_height: f32,
+    /// The maximum distance, in pixels, between a curved surface and
+    /// it's tessellated approximation. Default: `0.5`
+    pub tolerance_px: f32,
+    /// The minimum number of segments a full circle is divided into. Default: `12`
+    pub min_segments: u32,
+    /// The maximum number of segments a full circle is divided into. Default: `720`
+    pub max_segments: u32,
+    /// The factor by which the distance between the camera and a curved surface must change
+    /// before it is tessellated again. Default: `1.5`
+    pub hysteresis: f32,
+}
+
+impl TessellationBudget {
+    /// Create a budget, with default tolerances, for the given view.
+    #[inline]
+    #[must_use]
+    pub fn new(camera: impl Into<Point3<f32>>, fov: f32, viewport_height: f32) -> Self {
+        Self {
+            camera: camera.into(),
+            fov,
+            viewport_height,
+            tolerance_px: 0.5,
+            min_segments: 12,
+            max_segments: 720,
+            hysteresis: 1.5,
+        }
+    }
+
+    /// The distance between the camera and the nearest point of the
+    /// ball centered at `center`, of radius `extent`.
+    ///
+    /// Distances smaller than a thousandth of a unit (e.g. when the camera
+    /// is inside the ball) are clamped to that value.
+    #[inline]
+    #[must_use]
+    pub fn distance(&self, center: &Point3<f32>, extent: f32) -> f32 {
+        (nalgebra::distance(&self.camera, center) - extent).max(0.001)
+    }
+
+    /// The number of segments a full circle of radius `radius`, seen from `distance`,
+    /// should be divided into.
+    #[inline]
+    #[must_use]
+    pub fn segments_at(&self, radius: f32, distance: f32) -> u32 {
+        let radius_px = projected_radius_px(radius, distance, self.fov, self.viewport_height);
+
+        segments_for_chord_error(radius_px, self.tolerance_px)
+            .clamp(self.min_segments, self.max_segments)
+    }
+
+    /// Computes how finely a surface, lying in the ball centered at `center`, of radius
+    /// `extent`, and whose curves are circles of radius `radius`, should be tessellated.
+    #[inline]
+    #[must_use]
+    pub fn tessellate(
+        &self,
+        center: impl Into<Point3<f32>>,
+        extent: f32,
+        radius: f32,
+    ) -> Tessellation {
+        let center = center.into();
+        let distance = self.distance(&center, extent);
+
+        Tessellation {
+            segments: self.segments_at(radius, distance),
+            center,
+            extent,
+            radius,
+            distance,
+        }
+    }
+}
+
+/// The outcome of [`TessellationBudget::tessellate`]. Also records the conditions the
+/// tessellation was computed under, to determine when it should be recomputed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tessellation {
+    /// The number of segments full circles should be divided into.
+    pub segments: u32,
+    center: Point3<f32>,
+    extent: f32,
+    radius: f32,
+    distance: f32,
+}
+
+impl Tessellation {
+    /// Whether the surface this tessellation was computed for should be tessellated again,
+    /// under `budget`.
+    ///
+    /// This is the case when the distance between the camera and the surface has changed by
+    /// more than `budget.hysteresis`, and the number of segments changes accordingly.
+    #[inline]
+    #[must_use]
+    pub fn is_stale(&self, budget: &TessellationBudget) -> bool {
+        let distance = budget.distance(&self.center, self.extent);
+        let ratio = distance / self.distance;
+
+        !(budget.hysteresis.recip()..=budget.hysteresis).contains(&ratio)
+            && budget.segments_at(self.radius, distance) != self.segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::FRAC_PI_2;
+
+    // with a field of view of 90°, a unit circle `d` units away is `500 / d` pixels wide
+    fn budget_at(camera: [f32; 3]) -> TessellationBudget {
+        TessellationBudget::new(camera, FRAC_PI_2, 1000.)
+    }
+
+    // a unit circle whose nearest point is 10 units away from the origin
+    fn unit_circle() -> Tessellation {
+        budget_at([0.; 3]).tessellate([11., 0., 0.], 1., 1.)
+    }
+
+    // a camera `factor` times further away from `unit_circle` than the origin is
+    fn budget_scaled(factor: f32) -> TessellationBudget {
+        budget_at([10. - 10. * factor, 0., 0.])
+    }
+
+    #[test]
+    fn distance_is_measured_to_the_nearest_point() {
+        let budget = budget_at([0.; 3]);
+
+        assert_eq!(budget.distance(&[11., 0., 0.].into(), 1.), 10.);
+        // the camera is inside the ball
+        assert_eq!(budget.distance(&[0.5, 0., 0.].into(), 1.), 0.001);
+    }
+
+    #[test]
+    fn segments_are_clamped() {
+        let budget = budget_at([0.; 3]);
+
+        assert_eq!(budget.segments_at(1., 10.), 23);
+        assert_eq!(budget.segments_at(1e-3, 10.), budget.min_segments);
+        assert_eq!(budget.segments_at(1e3, 1e-3), budget.max_segments);
+    }
+
+    #[test]
+    fn unchanged_views_are_not_stale() {
+        let tessellation = unit_circle();
+
+        assert_eq!(tessellation.segments, 23);
+        assert!(!tessellation.is_stale(&budget_at([0.; 3])));
+    }
+
+    #[test]
+    fn moving_away_past_the_hysteresis_is_stale() {
+        let tessellation = unit_circle();
+
+        assert!(!tessellation.is_stale(&budget_scaled(1.49)));
+        assert!(tessellation.is_stale(&budget_scaled(1.51)));
+    }
+
+    #[test]
+    fn moving_closer_past_the_hysteresis_is_stale() {
+        let tessellation = unit_circle();
+
+        assert!(!tessellation.is_stale(&budget_scaled(1.49f32.recip())));
+        assert!(tessellation.is_stale(&budget_scaled(1.51f32.recip())));
+    }
+
+    #[test]
+    fn clamped_segments_are_never_stale() {
+        let budget = budget_at([0.; 3]);
+        let tessellation = budget.tessellate([11., 0., 0.], 1., 1e-3);
+
+        assert_eq!(tessellation.segments, budget.min_segments);
+        assert!(!tessellation.is_stale(&budget_scaled(10.)));
+    }
+}
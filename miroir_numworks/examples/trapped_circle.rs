@@ -3,9 +3,10 @@
 
 use core::panic::PanicInfo;
 
+use miroir::palette;
 use miroir_numworks::{
     eadk::{ion::*, kandinsky::*},
-    run_simulation, SimulationParams, SimulationRay,
+    run_simulation_mapped, ScreenMap, SimulationParams, SimulationRay, APP_RECT,
 };
 use miroir_shapes::{LineSegment, Sphere};
 
@@ -23,14 +24,7 @@ static ICON: [u8; 4250] = *include_bytes!("icon.nwi");
 
 #[no_mangle]
 fn main() {
-    fill_rect(
-        Rect {
-            point: Point { x: 0, y: 18 },
-            w: 320,
-            h: 222,
-        },
-        Color::from_rgb([78, 78, 78]),
-    );
+    fill_rect(APP_RECT, Color::from_rgb(palette::BACKGROUND));
 
     const NUMWORKS_COL: Color = Color::from_rgb([248, 180, 48]);
 
@@ -56,15 +50,16 @@ fn main() {
         )
     };
 
-    // coordinates of screen corners
-    const TOP_LEFT: [f32; 2] = [0., 18.];
-    const BOTTOM_RIGHT: [f32; 2] = [319., 239.];
-    const BOTTOM_LEFT: [f32; 2] = [0., 239.];
-    const TOP_RIGHT: [f32; 2] = [319., 18.];
-    const CENTER: [f32; 2] = [160., 129.];
+    // The scene is defined in world units, shared with `miroir_glium`'s `trapped_circle`
+    // example, and mapped to the part of the screen below the title bar when drawn
+    const TOP_LEFT: [f32; 2] = [-1.6, 1.1];
+    const BOTTOM_RIGHT: [f32; 2] = [1.6, -1.1];
+    const BOTTOM_LEFT: [f32; 2] = [-1.6, -1.1];
+    const TOP_RIGHT: [f32; 2] = [1.6, 1.1];
+    const CENTER: [f32; 2] = [0., 0.];
 
     let mirrors = (
-        Sphere::new(CENTER, 50.),
+        Sphere::new(CENTER, 0.5),
         [
             LineSegment::new([TOP_LEFT, TOP_RIGHT]),
             LineSegment::new([TOP_LEFT, BOTTOM_LEFT]),
@@ -73,9 +68,11 @@ fn main() {
         ],
     );
 
-    let rays = [SimulationRay::new([10., 50.], [2., 1.]).with_reflection_cap(200)];
+    let rays = [SimulationRay::new([-1.5, 0.8], [2., -1.]).with_reflection_cap(200)];
+
+    let map = ScreenMap::fit(BOTTOM_LEFT, TOP_RIGHT, APP_RECT, true);
 
-    run_simulation(
+    run_simulation_mapped(
         &mirrors,
         rays,
         SimulationParams {
@@ -85,6 +82,7 @@ fn main() {
             step_time_ms: 100,
             ..Default::default()
         },
+        &map,
     );
 
     while !{
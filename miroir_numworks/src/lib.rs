@@ -4,7 +4,8 @@ use core::ops::Deref;
 use eadk::kandinsky::*;
 use miroir::{
     nalgebra::{ComplexField, RealField, SVector, Unit},
-    Mirror, Ray, RayPath,
+    palette::Palette,
+    resolve_reflection_cap, ExclusionPolicy, Mirror, Prioritized, Ray, RayPath,
 };
 use num_traits::{float::FloatCore, AsPrimitive};
 
@@ -13,59 +14,53 @@ extern crate alloc;
 #[cfg(feature = "alloc")]
 use alloc::{boxed::Box, rc::Rc, sync::Arc, vec::Vec};
 
+pub use miroir::util::{fmt_fixed, MAX_FIXED_DECIMALS};
+
+mod screen_map;
+pub use screen_map::*;
+
 pub use eadk;
 
 /// A trait enabling [`Mirror`]s to be drawn on your Numworks Calculator's screen.
+///
+/// Mirrors are defined in world coordinates, `map` must be used to convert
+/// them to screen coordinates when drawing, see [`ScreenMap::to_pixel`].
 #[impl_trait_for_tuples::impl_for_tuples(16)]
-pub trait KandinskyRenderable {
-    fn draw(&self, color: Color);
+pub trait KandinskyRenderable<S> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>);
 }
 
-impl<S: RealField + AsPrimitive<i16>> KandinskyRenderable for miroir_shapes::Sphere<S, 2> {
-    fn draw(&self, color: Color) {
-        let [x, y] = self.center.into();
-
+impl<S: RealField + AsPrimitive<i16>> KandinskyRenderable<S> for miroir_shapes::Sphere<S, 2> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
         draw_circle(
-            Point {
-                x: x.as_(),
-                y: y.as_(),
-            },
-            self.radius().clone().as_().unsigned_abs(),
+            map.to_pixel(&self.center),
+            map.to_screen_length(*self.radius())
+                .round()
+                .as_()
+                .unsigned_abs(),
             color,
         );
     }
 }
 
-impl<S: RealField + AsPrimitive<i16>> KandinskyRenderable for miroir_shapes::LineSegment<S> {
-    fn draw(&self, color: Color) {
+impl<S: RealField + AsPrimitive<i16>> KandinskyRenderable<S> for miroir_shapes::LineSegment<S> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
         let [start, end] = self.vertices();
-        let [x0, y0] = start.into();
-        let [x1, y1] = end.into();
-        draw_line(
-            Point {
-                x: x0.as_(),
-                y: y0.as_(),
-            },
-            Point {
-                x: x1.as_(),
-                y: y1.as_(),
-            },
-            color,
-        );
+        draw_line(map.to_pixel(&start), map.to_pixel(&end), color);
     }
 }
 
-impl<T: KandinskyRenderable> KandinskyRenderable for [T] {
-    fn draw(&self, color: Color) {
+impl<S, T: KandinskyRenderable<S>> KandinskyRenderable<S> for [T] {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
         for mirror in self {
-            mirror.draw(color);
+            mirror.draw(color, map);
         }
     }
 }
 
-impl<const N: usize, T: KandinskyRenderable> KandinskyRenderable for [T; N] {
-    fn draw(&self, color: Color) {
-        self.as_slice().draw(color);
+impl<const N: usize, S, T: KandinskyRenderable<S>> KandinskyRenderable<S> for [T; N] {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.as_slice().draw(color, map);
     }
 }
 
@@ -73,42 +68,48 @@ impl<const N: usize, T: KandinskyRenderable> KandinskyRenderable for [T; N] {
 // types implementing `Deref` makes the trait unusable downstream
 
 #[cfg(feature = "alloc")]
-impl<T: KandinskyRenderable + ?Sized> KandinskyRenderable for Box<T> {
-    fn draw(&self, color: Color) {
-        self.deref().draw(color);
+impl<S, T: KandinskyRenderable<S> + ?Sized> KandinskyRenderable<S> for Box<T> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.deref().draw(color, map);
     }
 }
 
 #[cfg(feature = "alloc")]
-impl<T: KandinskyRenderable + ?Sized> KandinskyRenderable for Arc<T> {
-    fn draw(&self, color: Color) {
-        self.deref().draw(color);
+impl<S, T: KandinskyRenderable<S> + ?Sized> KandinskyRenderable<S> for Arc<T> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.deref().draw(color, map);
     }
 }
 
 #[cfg(feature = "alloc")]
-impl<T: KandinskyRenderable + ?Sized> KandinskyRenderable for Rc<T> {
-    fn draw(&self, color: Color) {
-        self.deref().draw(color);
+impl<S, T: KandinskyRenderable<S> + ?Sized> KandinskyRenderable<S> for Rc<T> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.deref().draw(color, map);
     }
 }
 
 #[cfg(feature = "alloc")]
-impl<T: KandinskyRenderable> KandinskyRenderable for Vec<T> {
-    fn draw(&self, color: Color) {
-        self.deref().draw(color);
+impl<S, T: KandinskyRenderable<S>> KandinskyRenderable<S> for Vec<T> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.deref().draw(color, map);
+    }
+}
+
+impl<S, T: KandinskyRenderable<S> + ?Sized> KandinskyRenderable<S> for &T {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        (*self).draw(color, map);
     }
 }
 
-impl<'a, T: KandinskyRenderable + ?Sized> KandinskyRenderable for &'a T {
-    fn draw(&self, color: Color) {
-        (*self).draw(color);
+impl<S, T: KandinskyRenderable<S> + ?Sized> KandinskyRenderable<S> for &mut T {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.deref().draw(color, map);
     }
 }
 
-impl<'a, T: KandinskyRenderable + ?Sized> KandinskyRenderable for &'a mut T {
-    fn draw(&self, color: Color) {
-        self.deref().draw(color);
+impl<S, T: KandinskyRenderable<S>> KandinskyRenderable<S> for Prioritized<T> {
+    fn draw(&self, color: Color, map: &ScreenMap<S>) {
+        self.0.draw(color, map);
     }
 }
 
@@ -120,10 +121,15 @@ pub struct SimulationRay<S, const D: usize> {
     pub ray: Ray<S, D>,
     /// The maximum amount of reflections this ray will do. If this is `Some(n)` the ray
     /// will perform at most `n` reflections. Default: `None`
+    ///
+    /// If this is `None`, [`SimulationParams::reflection_cap`] is used instead. `Some(0)`
+    /// means the ray doesn't reflect at all, nor is it drawn past it's origin.
     pub reflection_cap: Option<usize>,
-    /// Color of the lines drawn on screen representing the ray's path.
-    /// Default: [`Self::DEFAULT_COLOR`]
-    pub color: Color,
+    /// Color of the lines drawn on screen representing the ray's path. If this is `None`,
+    /// the ray is assigned a color from [`SimulationParams::palette`], according to it's
+    /// position among the simulation's rays, or [`Self::DEFAULT_COLOR`] if that's `None` too.
+    /// Default: `None`
+    pub color: Option<Color>,
 }
 
 impl<const D: usize, S: PartialEq> PartialEq for SimulationRay<S, D> {
@@ -143,7 +149,7 @@ impl<S, const D: usize> SimulationRay<S, D> {
         Self {
             ray,
             reflection_cap: None,
-            color: Self::DEFAULT_COLOR,
+            color: None,
         }
     }
 
@@ -153,16 +159,6 @@ impl<S, const D: usize> SimulationRay<S, D> {
         Self::from_ray(Ray::new_unit_dir(origin, dir))
     }
 
-    #[inline]
-    #[must_use]
-    /// Does not normalize `dir`
-    pub fn new_unchecked_dir(
-        origin: impl Into<SVector<S, D>>,
-        dir: impl Into<SVector<S, D>>,
-    ) -> Self {
-        Self::from_ray(Ray::new_unchecked_dir(origin, dir))
-    }
-
     #[inline]
     #[must_use]
     pub fn with_reflection_cap(mut self, max: usize) -> Self {
@@ -173,7 +169,7 @@ impl<S, const D: usize> SimulationRay<S, D> {
     #[inline]
     #[must_use]
     pub fn with_color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.color = Some(color);
         self
     }
 }
@@ -193,6 +189,16 @@ impl<S: ComplexField, const D: usize> SimulationRay<S, D> {
     pub fn new(origin: impl Into<SVector<S, D>>, dir: impl Into<SVector<S, D>>) -> Self {
         Self::from_ray(Ray::new(origin, dir))
     }
+
+    #[inline]
+    #[must_use]
+    /// Does not normalize `dir`, see [`Ray::new_unchecked_dir`]
+    pub fn new_unchecked_dir(
+        origin: impl Into<SVector<S, D>>,
+        dir: impl Into<SVector<S, D>>,
+    ) -> Self {
+        Self::from_ray(Ray::new_unchecked_dir(origin, dir))
+    }
 }
 
 /// A set of global parameters for a simulation.
@@ -207,6 +213,17 @@ pub struct SimulationParams<S> {
     pub mirror_color: Color,
     /// A pause time between each reflection, useful for easily viewing the ray's path.
     pub step_time_ms: u32,
+    /// The maximum amount of reflections performed by rays that don't
+    /// specify one themselves, `None` for no limit. Default: `None`
+    ///
+    /// See [`resolve_reflection_cap`] for more info.
+    pub reflection_cap: Option<usize>,
+    /// How rays are prevented from intersecting again with the surface they have just been
+    /// reflected by. Default: [`ExclusionPolicy::EpsTravel`]
+    pub exclusion_policy: ExclusionPolicy,
+    /// The palette rays that don't specify a color are assigned one from, `None` to
+    /// draw them all with [`SimulationRay::DEFAULT_COLOR`]. Default: `None`
+    pub palette: Option<Palette>,
 }
 
 impl<S: FloatCore + 'static> Default for SimulationParams<S>
@@ -218,52 +235,77 @@ where
             epsilon: S::epsilon() * 64.0.as_(),
             mirror_color: Color::from_rgb([255, 0, 0]),
             step_time_ms: 0,
+            reflection_cap: None,
+            exclusion_policy: ExclusionPolicy::EpsTravel,
+            palette: None,
         }
     }
 }
 
+/// Runs a simulation, and draws it on screen, using world coordinates as screen coordinates.
+///
+/// See [`run_simulation_mapped`] to draw simulations defined in other units.
 pub fn run_simulation<M>(
     mirror: &M,
     rays: impl IntoIterator<Item = SimulationRay<M::Scalar, 2>>,
     params: SimulationParams<M::Scalar>,
 ) where
-    M: Mirror<2, Scalar: RealField + AsPrimitive<i16>> + KandinskyRenderable + ?Sized,
+    M: Mirror<2, Scalar: RealField + AsPrimitive<i16>> + KandinskyRenderable<M::Scalar> + ?Sized,
+    f64: AsPrimitive<M::Scalar>,
+{
+    run_simulation_mapped(mirror, rays, params, &ScreenMap::identity());
+}
+
+/// Runs a simulation, and draws it on screen, using `map` to convert world coordinates
+/// to screen coordinates.
+///
+/// The simulation itself is run in world coordinates, (`params.epsilon` is in world units),
+/// only drawing is affected by `map`.
+pub fn run_simulation_mapped<M>(
+    mirror: &M,
+    rays: impl IntoIterator<Item = SimulationRay<M::Scalar, 2>>,
+    params: SimulationParams<M::Scalar>,
+    map: &ScreenMap<M::Scalar>,
+) where
+    M: Mirror<2, Scalar: RealField + AsPrimitive<i16>> + KandinskyRenderable<M::Scalar> + ?Sized,
     f64: AsPrimitive<M::Scalar>,
 {
-    mirror.draw(params.mirror_color);
+    mirror.draw(params.mirror_color, map);
+
+    // length, in world units, of the line drawn in the direction of
+    // diverging rays, so that it spans 1000 pixels on screen
+    let divergence_length = 1000.0.as_() / map.scale().abs();
 
-    for SimulationRay {
-        ray,
-        reflection_cap,
-        color,
-    } in rays
+    for (
+        i,
+        SimulationRay {
+            ray,
+            reflection_cap,
+            color,
+        },
+    ) in rays.into_iter().enumerate()
     {
+        let color = color
+            .or_else(|| {
+                params
+                    .palette
+                    .map(|palette| Color::from_rgb(palette.color(i)))
+            })
+            .unwrap_or(SimulationRay::<M::Scalar, 2>::DEFAULT_COLOR);
+
         let mut prev_pt = ray.origin;
-        let mut path = RayPath {
-            mirror,
-            ray,
-            eps: params.epsilon.clone(),
-        };
+        let mut path =
+            RayPath::new(ray, mirror, params.epsilon).with_policy(params.exclusion_policy);
 
         let connect_line = |prev: &mut SVector<_, 2>, to: SVector<_, 2>| {
-            let [x0, y0]: [M::Scalar; 2] = (*prev).into();
+            draw_line(map.to_pixel(prev), map.to_pixel(&to), color);
             *prev = to;
-            let [x1, y1] = to.into();
-            draw_line(
-                Point {
-                    x: x0.as_(),
-                    y: y0.as_(),
-                },
-                Point {
-                    x: x1.as_(),
-                    y: y1.as_(),
-                },
-                color,
-            );
             eadk::time::sleep_ms(params.step_time_ms);
         };
 
-        let diverges = if let Some(n) = reflection_cap {
+        let cap = resolve_reflection_cap(reflection_cap, params.reflection_cap, None);
+
+        let diverges = if let Some(n) = cap {
             let mut count = 0;
             for Ray { origin, .. } in path.by_ref().take(n) {
                 connect_line(&mut prev_pt, origin);
@@ -278,7 +320,7 @@ pub fn run_simulation<M>(
         };
 
         if diverges {
-            let new_pt = prev_pt + path.ray.dir.as_ref() * 1000.0.as_();
+            let new_pt = prev_pt + path.ray().dir.as_ref() * divergence_length;
             connect_line(&mut prev_pt, new_pt);
         }
     }
@@ -0,0 +1,322 @@
+//! Mapping world coordinates (those mirrors and rays are defined in) to screen coordinates
+//! (those things are drawn with), on the calculator's pixel grid.
+
+use super::*;
+
+/// The full screen of the calculator, in pixels.
+pub const SCREEN_RECT: Rect = Rect {
+    point: Point { x: 0, y: 0 },
+    w: 320,
+    h: 240,
+};
+
+/// The part of the screen below the status bar, in pixels.
+pub const APP_RECT: Rect = Rect {
+    point: Point { x: 0, y: 18 },
+    w: 320,
+    h: 222,
+};
+
+/// A mapping from world coordinates to screen coordinates.
+///
+/// The mapping is a uniform scaling followed by a translation, optionally flipping the
+/// y axis, so that shapes are never distorted (circles stay circles).
+///
+/// Screen coordinates are pixel centers: the pixel at column `x` and row `y` is centered at
+/// `[x, y]`, and covers `[x - 0.5, x + 0.5] × [y - 0.5, y + 0.5]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenMap<S> {
+    /// Screen position of the world's origin.
+    origin: SVector<S, 2>,
+    /// Pixels per world unit, along each axis (the y one is negative when flipping).
+    scale: SVector<S, 2>,
+}
+
+impl<S: RealField> ScreenMap<S> {
+    /// The mapping where world coordinates are screen coordinates.
+    #[inline]
+    #[must_use]
+    pub fn identity() -> Self {
+        Self {
+            origin: SVector::zeros(),
+            scale: SVector::repeat(S::one()),
+        }
+    }
+
+    /// Create a mapping fitting the axis-aligned box with opposite corners `world_min` and
+    /// `world_max` in the screen rectangle whose top left and bottom right pixels are
+    /// `screen_min` and `screen_max`, as large as possible without distorting it, and
+    /// centered along the axis it doesn't fill (letterboxing).
+    ///
+    /// If `flip_y` is `true`, the world's y axis points up, (the screen's one points down).
+    ///
+    /// The box's edges are mapped to the centers of the rectangle's outermost pixels, e.g.
+    /// if the box fills the whole width of the rectangle, `world_min.x` is mapped to
+    /// `screen_min.x`, and `world_max.x` to `screen_max.x`. Rounding the image of any point
+    /// of the box thus always yields a pixel inside the rectangle.
+    ///
+    /// Returns `None` if the box, or the rectangle, has a non-positive (or non-finite)
+    /// width or height.
+    #[inline]
+    #[must_use]
+    pub fn try_new(
+        world_min: impl Into<SVector<S, 2>>,
+        world_max: impl Into<SVector<S, 2>>,
+        screen_min: impl Into<SVector<S, 2>>,
+        screen_max: impl Into<SVector<S, 2>>,
+        flip_y: bool,
+    ) -> Option<Self> {
+        let (world_min, world_max) = (world_min.into(), world_max.into());
+        let screen_min = screen_min.into();
+        let world_size = &world_max - &world_min;
+        let screen_size = screen_max.into() - &screen_min;
+
+        let is_valid = |c: &S| c.is_finite() && c > &S::zero();
+
+        if !world_size.iter().chain(screen_size.iter()).all(is_valid) {
+            return None;
+        }
+
+        let scale = (screen_size.x.clone() / world_size.x.clone())
+            .min(screen_size.y.clone() / world_size.y.clone());
+
+        let two = S::one() + S::one();
+        // screen position of `world_min`, before flipping
+        let corner = screen_min + (screen_size - &world_size * scale.clone()) / two;
+
+        let (origin_y, scale_y) = if flip_y {
+            // `world_max.y` is at the top
+            (
+                corner.y.clone() + world_max.y.clone() * scale.clone(),
+                -scale.clone(),
+            )
+        } else {
+            (
+                corner.y.clone() - world_min.y.clone() * scale.clone(),
+                scale.clone(),
+            )
+        };
+
+        let origin_x = corner.x.clone() - world_min.x.clone() * scale.clone();
+
+        Some(Self {
+            origin: SVector::from([origin_x, origin_y]),
+            scale: SVector::from([scale, scale_y]),
+        })
+    }
+
+    /// # Panics
+    ///
+    /// See [`Self::try_new`].
+    #[inline]
+    #[must_use]
+    pub fn new(
+        world_min: impl Into<SVector<S, 2>>,
+        world_max: impl Into<SVector<S, 2>>,
+        screen_min: impl Into<SVector<S, 2>>,
+        screen_max: impl Into<SVector<S, 2>>,
+        flip_y: bool,
+    ) -> Self {
+        Self::try_new(world_min, world_max, screen_min, screen_max, flip_y)
+            .expect("empty world box, or screen rectangle")
+    }
+
+    /// The number of pixels a world unit spans.
+    #[inline]
+    #[must_use]
+    pub fn scale(&self) -> S {
+        self.scale.x.clone()
+    }
+
+    /// Maps a point in world coordinates to (unrounded) screen coordinates.
+    #[inline]
+    #[must_use]
+    pub fn to_screen(&self, p: &SVector<S, 2>) -> SVector<S, 2> {
+        &self.origin + p.component_mul(&self.scale)
+    }
+
+    /// Maps a point in screen coordinates to world coordinates.
+    #[inline]
+    #[must_use]
+    pub fn to_world(&self, p: &SVector<S, 2>) -> SVector<S, 2> {
+        (p - &self.origin).component_div(&self.scale)
+    }
+
+    /// Maps a length in world units to a length in pixels.
+    #[inline]
+    #[must_use]
+    pub fn to_screen_length(&self, length: S) -> S {
+        length * self.scale()
+    }
+}
+
+impl<S: RealField> ScreenMap<S> {
+    /// Create a mapping fitting the axis-aligned box with opposite corners `world_min` and
+    /// `world_max` in `screen`, see [`Self::try_new`].
+    ///
+    /// The box's edges are mapped to `screen`'s outermost pixels, e.g. if the box fills the
+    /// whole width of [`APP_RECT`], `world_max.x` is mapped to it's last column, `x = 319`.
+    ///
+    /// Returns `None` if the box has a non-positive (or non-finite) width or height,
+    /// or if `screen` is less than 2 pixels wide or tall.
+    #[inline]
+    #[must_use]
+    pub fn try_fit(
+        world_min: impl Into<SVector<S, 2>>,
+        world_max: impl Into<SVector<S, 2>>,
+        screen: Rect,
+        flip_y: bool,
+    ) -> Option<Self> {
+        let min = pixel_center(screen.point);
+        let size = SVector::from([screen.w, screen.h].map(|c| S::from_subset(&f64::from(c))));
+        let max = &min + size - SVector::repeat(S::one());
+
+        Self::try_new(world_min, world_max, min, max, flip_y)
+    }
+
+    /// # Panics
+    ///
+    /// See [`Self::try_fit`].
+    #[inline]
+    #[must_use]
+    pub fn fit(
+        world_min: impl Into<SVector<S, 2>>,
+        world_max: impl Into<SVector<S, 2>>,
+        screen: Rect,
+        flip_y: bool,
+    ) -> Self {
+        Self::try_fit(world_min, world_max, screen, flip_y)
+            .expect("empty world box, or screen rectangle")
+    }
+
+    /// Maps (the center of) a screen pixel to world coordinates.
+    #[inline]
+    #[must_use]
+    pub fn pixel_to_world(&self, p: Point) -> SVector<S, 2> {
+        self.to_world(&pixel_center(p))
+    }
+}
+
+impl<S: RealField + AsPrimitive<i16>> ScreenMap<S> {
+    /// Maps a point to the screen pixel it lands on.
+    ///
+    /// Coordinates outside of the range of [`i16`] are saturated.
+    #[inline]
+    #[must_use]
+    pub fn to_pixel(&self, p: &SVector<S, 2>) -> Point {
+        let [x, y] = self.to_screen(p).map(|c| c.round().as_()).into();
+        Point { x, y }
+    }
+}
+
+fn pixel_center<S: RealField>(Point { x, y }: Point) -> SVector<S, 2> {
+    SVector::from([x, y].map(|c| S::from_subset(&f64::from(c))))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    // the Numworks calculator's screen, below the status bar
+    const SCREEN_MIN: [f64; 2] = [0., 18.];
+    const SCREEN_MAX: [f64; 2] = [319., 239.];
+
+    fn map(world_min: [f64; 2], world_max: [f64; 2], flip_y: bool) -> ScreenMap<f64> {
+        ScreenMap::new(world_min, world_max, SCREEN_MIN, SCREEN_MAX, flip_y)
+    }
+
+    fn assert_maps(map: &ScreenMap<f64>, world: [f64; 2], screen: [f64; 2]) {
+        let (world, screen) = (SVector::from(world), SVector::from(screen));
+
+        assert!((map.to_screen(&world) - screen).norm() < 1e-9);
+        assert!((map.to_world(&screen) - world).norm() < 1e-9);
+    }
+
+    #[test]
+    fn identity() {
+        let map = ScreenMap::identity();
+
+        assert_maps(&map, [12., -3.], [12., -3.]);
+        assert_eq!(map.to_screen_length(2.), 2.);
+    }
+
+    #[test]
+    fn box_corners_map_to_outermost_pixels() {
+        // same aspect ratio as the screen rectangle
+        let map = map([0., 0.], [31.9, 22.1], false);
+
+        assert_eq!(map.scale(), 10.);
+        assert_maps(&map, [0., 0.], SCREEN_MIN);
+        assert_maps(&map, [31.9, 22.1], SCREEN_MAX);
+        assert_maps(&map, [31.9 / 2., 22.1 / 2.], [159.5, 128.5]);
+    }
+
+    #[test]
+    fn flipping_swaps_top_and_bottom() {
+        let map = map([0., 0.], [31.9, 22.1], true);
+
+        assert_maps(&map, [0., 22.1], SCREEN_MIN);
+        assert_maps(&map, [31.9, 0.], SCREEN_MAX);
+        assert_maps(&map, [0., 0.], [0., 239.]);
+        assert_eq!(map.to_screen_length(1.), 10.);
+    }
+
+    #[test]
+    fn wide_boxes_are_letterboxed_vertically() {
+        // wider than the screen rectangle, relative to it's height
+        let map = map([-2., -1.], [2., 1.], true);
+
+        // 319 pixels for 4 units, the box is 159.5 pixels tall, centered vertically
+        let scale = 319. / 4.;
+        let top = 18. + (221. - 2. * scale) / 2.;
+
+        assert_eq!(map.scale(), scale);
+        assert_maps(&map, [-2., 1.], [0., top]);
+        assert_maps(&map, [2., -1.], [319., top + 2. * scale]);
+        assert_maps(&map, [0., 0.], [159.5, 128.5]);
+    }
+
+    #[test]
+    fn tall_boxes_are_letterboxed_horizontally() {
+        let map = map([0., 0.], [1., 2.], false);
+
+        // 221 pixels for 2 units, the box is 110.5 pixels wide, centered horizontally
+        let scale = 221. / 2.;
+        let left = (319. - scale) / 2.;
+
+        assert_eq!(map.scale(), scale);
+        assert_maps(&map, [0., 0.], [left, 18.]);
+        assert_maps(&map, [1., 2.], [left + scale, 239.]);
+    }
+
+    #[test]
+    fn box_points_round_to_pixels_of_the_rectangle() {
+        let map = map([-1.6, -1.1], [1.6, 1.1], true);
+
+        for i in 0..=100 {
+            for j in 0..=100 {
+                let p = [-1.6 + 3.2 * i as f64 / 100., -1.1 + 2.2 * j as f64 / 100.];
+                let pixel = map.to_screen(&p.into()).map(f64::round);
+
+                assert!((SCREEN_MIN[0]..=SCREEN_MAX[0]).contains(&pixel.x));
+                assert!((SCREEN_MIN[1]..=SCREEN_MAX[1]).contains(&pixel.y));
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_boxes_and_rectangles_are_rejected() {
+        let screen = |world_min, world_max| {
+            ScreenMap::try_new(world_min, world_max, SCREEN_MIN, SCREEN_MAX, false)
+        };
+
+        assert!(screen([0., 0.], [0., 1.]).is_none());
+        assert!(screen([0., 1.], [1., 0.]).is_none());
+        assert!(screen([0., 0.], [f64::INFINITY, 1.]).is_none());
+        assert!(screen([0., 0.], [f64::NAN, 1.]).is_none());
+        assert!(ScreenMap::try_new([0., 0.], [1., 1.], [0., 0.], [0., 10.], false).is_none());
+        assert!(ScreenMap::try_new([0., 0.], [1., 1.], [5., 0.], [0., 10.], false).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Checks every shape of this crate against [`miroir::conformance`].
+
+use super::*;
+use miroir::conformance::{run_conformance, ConformanceConfig};
+
+/// Probes `mirror` with rays starting in the ball of radius `radius` around `center`.
+///
+/// `reversible` is whether some of these rays reflect at least twice, making the
+/// reversibility check applicable.
+#[track_caller]
+fn assert_conforms<const D: usize>(
+    mirror: &impl Mirror<D, Scalar = f64>,
+    center: [f64; D],
+    radius: f64,
+    reversible: bool,
+) {
+    let report = run_conformance(mirror, &ConformanceConfig::new(center, radius, 1e-9, 1e-6));
+
+    assert!(report.hits > 0, "{report:#?}");
+
+    for (name, check) in report.checks() {
+        assert!(check.passed(), "{name} failed: {report:#?}");
+    }
+
+    assert_eq!(report.reversibility.checked > 0, reversible, "{report:#?}");
+}
+
+#[test]
+fn circle_conforms() {
+    let circle = Sphere::<f64, 2>::new([0.3, -0.2], 1.5);
+
+    // from inside, rays bounce around indefinitely
+    assert_conforms(&circle, [0.3, -0.2], 1.4, true);
+    assert_conforms(&circle, [0., 0.], 4., true);
+}
+
+#[test]
+fn sphere_conforms() {
+    let sphere = Sphere::<f64, 3>::new([0.3, -0.2, 1.], 1.5);
+
+    assert_conforms(&sphere, [0.3, -0.2, 1.], 1.4, true);
+    assert_conforms(&sphere, [0., 0., 0.], 4., true);
+}
+
+#[test]
+fn line_segment_conforms() {
+    let segment = LineSegment::<f64>::new([[-1., 0.5], [2., -0.25]]);
+
+    assert_conforms(&segment, [0., 0.], 3., false);
+}
+
+#[test]
+fn triangle_conforms() {
+    let triangle = Triangle::<f64>::new([[-1., 0.5, 0.], [2., -0.25, 0.3], [0., 1., 1.]]);
+
+    assert_conforms(&triangle, [0., 0., 0.], 3., false);
+}
+
+#[test]
+fn cylinder_conforms() {
+    let cylinder = Cylinder::<f64>::new([0., 0., -1.], [0.2, 0.1, 1.], 0.5);
+
+    assert_conforms(&cylinder, [0.1, 0.05, 0.], 0.4, true);
+    assert_conforms(&cylinder, [0., 0., 0.], 3., true);
+}
+
+#[test]
+fn composite_conforms() {
+    let square = [
+        LineSegment::new([[-2., -2.], [2., -2.]]),
+        LineSegment::new([[2., -2.], [2., 2.]]),
+        LineSegment::new([[2., 2.], [-2., 2.]]),
+        LineSegment::new([[-2., 2.], [-2., -2.]]),
+    ];
+
+    let scene = (square, Sphere::<f64, 2>::new([0.5, 0.3], 0.7));
+
+    assert_conforms(&scene, [-1., -1.], 0.9, true);
+}
@@ -36,6 +36,30 @@ impl<S: RealField> Cylinder<S> {
         }
     }
 
+    /// Like [`Self::new`], but returns `None` if the segment's endpoints are equal,
+    /// or if `radius` is too small, relative to their norms, for the normals computed
+    /// on this cylinder to be accurate (see [`MIN_RELATIVE_RADIUS_ULPS`]), or zero,
+    /// or not finite.
+    #[inline]
+    #[must_use]
+    pub fn try_new(
+        segment_start: impl Into<SVector<S, 3>>,
+        segment_end: impl Into<SVector<S, 3>>,
+        radius: S,
+    ) -> Option<Self> {
+        let cylinder = Self::new(segment_start, segment_end, radius.abs());
+        cylinder.is_valid().then_some(cylinder)
+    }
+
+    /// See [`Self::try_new`].
+    #[inline]
+    fn is_valid(&self) -> bool {
+        let [start, end] = self.line_segment();
+        let magnitude = start.norm().max(end.norm());
+
+        self.inv_norm_dist_squared.is_finite() && is_valid_radius(self.radius(), magnitude)
+    }
+
     #[inline]
     #[must_use]
     pub const fn start(&self) -> &SVector<S, 3> {
@@ -60,6 +84,7 @@ impl<S: RealField> Cylinder<S> {
         &self.radius
     }
 
+    /// Note that no validation is done on `radius`, see [`Self::try_new`].
     #[inline]
     pub fn set_radius(&mut self, radius: S) {
         self.radius = radius.clone().abs();
@@ -105,10 +130,11 @@ impl<S: RealField> Cylinder<S> {
                 if (S::zero()..=S::one()).contains(&coord) {
                     let line_pt = &self.start + self.dist.clone() * coord;
 
-                    out.push((
-                        t,
-                        Unit::new_unchecked((origin - line_pt).unscale(self.radius.clone())),
-                    ));
+                    // SAFETY: origin is on the cylinder, and line_pt is it's
+                    // orthogonal projection on the axis, so they are radius apart
+                    let n = (origin - line_pt).unscale(self.radius.clone());
+                    debug_assert_unit!(n, self);
+                    out.push((t, Unit::new_unchecked(n)));
                 }
             }
         }
@@ -117,11 +143,132 @@ impl<S: RealField> Cylinder<S> {
     }
 }
 
+impl<S: RealField> LerpParams<S> for Cylinder<S> {
+    /// The start, and direction, of the cylinder's segment are interpolated, along with it's
+    /// radius, then validated like [`Self::try_new`] does.
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        let dist = lerp_vector(&self.dist, &other.dist, &t);
+        let radius = lerp(&self.radius, &other.radius, &t).abs();
+
+        let cylinder = Self {
+            start: lerp_vector(&self.start, &other.start, &t),
+            inv_norm_dist_squared: dist.norm_squared().recip(),
+            dist,
+            radius_sq: radius.clone() * radius.clone(),
+            radius,
+        };
+
+        if cylinder.is_valid() {
+            Ok(cylinder)
+        } else {
+            Err(LerpError::InvalidShape)
+        }
+    }
+}
+
+impl<S: RealField> ReflectAcross<S, 3> for Cylinder<S> {
+    /// The reflected cylinder is validated like [`Self::try_new`] does, since it's segment
+    /// may be farther from the origin.
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, 3>) -> Option<Self> {
+        let cylinder = Self {
+            start: reflect_point_across(&self.start, plane),
+            dist: reflect_vector_across(&self.dist, plane),
+            ..self.clone()
+        };
+
+        cylinder.is_valid().then_some(cylinder)
+    }
+}
+
 impl<S: RealField> Mirror<3> for Cylinder<S> {
     type Scalar = S;
     fn add_tangents(&self, ctx: &mut SimulationCtx<Self::Scalar, 3>) {
         for (d, n) in self.tangents_at_intersections(ctx.ray()) {
-            ctx.add_tangent(d, Hyperplane::Normal(n));
+            let tangent = Hyperplane::Normal(n);
+            let condition = tangent.incidence_condition(&ctx.ray().dir);
+            ctx.add_tangent_with_condition(d, tangent, condition);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[test]
+    fn conditions() {
+        let cylinder = Cylinder::<f64>::new([0., 0., -1.], [0., 0., 1.], 0.5);
+        let condition = |ray: Ray<f64, 3>| {
+            let (_, _, condition) = ray
+                .closest_intersection_with_condition(&cylinder, 1e-9)
+                .unwrap();
+            condition
+        };
+
+        let head_on = condition(Ray::new([-2., 0., 0.], [1., 0., 0.]));
+        assert!((head_on - 1.).abs() < 1e-12);
+
+        // tangent to the cylinder along the line `x = 0, y = 0.5`
+        let grazing = condition(Ray::new([-2., 0.5 - 1e-8, 0.], [1., 0., 0.]));
+        assert!(grazing > 1e3);
+    }
+
+    #[test]
+    fn degenerate_cylinders_are_rejected() {
+        assert!(Cylinder::<f64>::try_new([1., 2., 3.], [1., 2., 3.], 1.).is_none());
+        assert!(Cylinder::<f64>::try_new([0., 0., 0.], [f64::NAN, 0., 1.], 1.).is_none());
+
+        for radius in [0., f64::NAN, f64::INFINITY] {
+            assert!(Cylinder::<f64>::try_new([0., 0., 0.], [0., 0., 1.], radius).is_none());
+        }
+
+        // the radius is compared to the norm of the farthest endpoint
+        let end = [0., 0., 1e3];
+        let radius = min_radius_f32(1e3) * 0.9;
+        assert!(Cylinder::<f32>::try_new([0., 0., 0.], end, radius).is_none());
+    }
+
+    #[test]
+    fn small_f32_radii_are_accepted() {
+        assert!(Cylinder::<f32>::try_new([1., 0., 0.], [1., 0., 1.], 0.1).is_some());
+        // negative radii are made positive
+        assert!(Cylinder::<f32>::try_new([0., 0., 0.], [1., 0., 0.], -1.).is_some());
+    }
+
+    /// Cylinders accepted by `try_new`, with radii as small as possible, far from the origin,
+    /// never compute normals tripping `debug_assert_unit`.
+    #[test]
+    #[cfg(debug_assertions)]
+    fn accepted_cylinders_compute_unit_normals() {
+        use nalgebra::Vector3;
+
+        let mut rng = Rng::new(0x3C6E_F372_FE94_F82B);
+        let mut accepted = 0;
+
+        for _ in 0..10_000 {
+            let scale = 10f32.powf(rng.float() * 4.);
+            let start = Vector3::new(rng.float(), rng.float(), rng.float()) * scale;
+            let axis = Vector3::new(rng.float(), rng.float(), rng.float()) * scale;
+
+            let radius = min_radius_f32(start.norm().max((start + axis).norm()))
+                * (1. + 4. * (rng.float() + 1.));
+
+            // rays starting around the cylinder, pointing towards the middle of it's axis
+            let middle = start + axis / 2.;
+            let offset = Vector3::new(rng.float(), rng.float(), rng.float()) * radius * 4.;
+            let Some(ray) = Ray::try_new(middle + offset, -offset) else {
+                continue;
+            };
+
+            if let Some(cylinder) = Cylinder::<f32>::try_new(start, start + axis, radius) {
+                accepted += 1;
+                let _ = cylinder.tangents_at_intersections(&ray);
+            }
+        }
+
+        assert!(accepted > 5_000, "{accepted}");
+    }
+}
@@ -0,0 +1,456 @@
+//! Robust 2D geometric predicates over `f64`.
+//!
+//! This is a minimal port of the adaptive-precision `orient2d` predicate from Jonathan
+//! Richard Shewchuk's "Adaptive Precision Floating-Point Arithmetic and Fast Robust
+//! Geometric Predicates" (`predicates.c`, public domain). Only the parts needed for
+//! `orient2d` are included.
+
+// 2^-53, half an ulp of 1.0
+const EPSILON: f64 = f64::EPSILON / 2.;
+// 2^ceil(53 / 2) + 1, used to split a double into two non-overlapping halves
+const SPLITTER: f64 = 134_217_729.;
+
+const RESULT_ERR_BOUND: f64 = (3. + 8. * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_A: f64 = (3. + 16. * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_B: f64 = (2. + 12. * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_C: f64 = (9. + 64. * EPSILON) * EPSILON * EPSILON;
+
+#[inline]
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virt = x - a;
+    (x, b - b_virt)
+}
+
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virt = x - a;
+    let a_virt = x - b_virt;
+    (x, (a - a_virt) + (b - b_virt))
+}
+
+#[inline]
+fn two_diff_tail(a: f64, b: f64, x: f64) -> f64 {
+    let b_virt = a - x;
+    let a_virt = x + b_virt;
+    (a - a_virt) + (b_virt - b)
+}
+
+#[inline]
+fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    let x = a - b;
+    (x, two_diff_tail(a, b, x))
+}
+
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let a_big = c - a;
+    let a_hi = c - a_big;
+    (a_hi, a - a_hi)
+}
+
+#[inline]
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    let err1 = x - a_hi * b_hi;
+    let err2 = err1 - a_lo * b_hi;
+    let err3 = err2 - a_hi * b_lo;
+    (x, a_lo * b_lo - err3)
+}
+
+/// Computes `(a1 + a0) - (b1 + b0)` exactly, as a 4-component expansion
+/// (least significant component first).
+#[inline]
+fn two_two_diff(a1: f64, a0: f64, b1: f64, b0: f64) -> [f64; 4] {
+    let (i, x0) = two_diff(a0, b0);
+    let (j, zero) = two_sum(a1, i);
+    let (i, x1) = two_diff(zero, b1);
+    let (x3, x2) = two_sum(j, i);
+    [x0, x1, x2, x3]
+}
+
+/// Sums two (nonoverlapping, increasing magnitude) expansions `e` and `f` into `h`,
+/// eliminating zero components. Returns the length of the resulting expansion.
+///
+/// `h` must be at least `e.len() + f.len()` long.
+fn fast_expansion_sum_zeroelim(e: &[f64], f: &[f64], h: &mut [f64]) -> usize {
+    let at = |s: &[f64], i: usize| s.get(i).copied().unwrap_or(0.);
+
+    let (mut e_index, mut f_index) = (0, 0);
+    let (mut e_now, mut f_now) = (e[0], f[0]);
+
+    let mut q;
+    if (f_now > e_now) == (f_now > -e_now) {
+        q = e_now;
+        e_index += 1;
+        e_now = at(e, e_index);
+    } else {
+        q = f_now;
+        f_index += 1;
+        f_now = at(f, f_index);
+    }
+
+    let mut h_index = 0;
+    let mut push = |h: &mut [f64], hh: f64| {
+        if hh != 0. {
+            h[h_index] = hh;
+            h_index += 1;
+        }
+    };
+
+    if e_index < e.len() && f_index < f.len() {
+        let (q_new, hh) = if (f_now > e_now) == (f_now > -e_now) {
+            let sum = fast_two_sum(e_now, q);
+            e_index += 1;
+            e_now = at(e, e_index);
+            sum
+        } else {
+            let sum = fast_two_sum(f_now, q);
+            f_index += 1;
+            f_now = at(f, f_index);
+            sum
+        };
+        q = q_new;
+        push(h, hh);
+
+        while e_index < e.len() && f_index < f.len() {
+            let (q_new, hh) = if (f_now > e_now) == (f_now > -e_now) {
+                let sum = two_sum(q, e_now);
+                e_index += 1;
+                e_now = at(e, e_index);
+                sum
+            } else {
+                let sum = two_sum(q, f_now);
+                f_index += 1;
+                f_now = at(f, f_index);
+                sum
+            };
+            q = q_new;
+            push(h, hh);
+        }
+    }
+
+    while e_index < e.len() {
+        let (q_new, hh) = two_sum(q, e_now);
+        e_index += 1;
+        e_now = at(e, e_index);
+        q = q_new;
+        push(h, hh);
+    }
+
+    while f_index < f.len() {
+        let (q_new, hh) = two_sum(q, f_now);
+        f_index += 1;
+        f_now = at(f, f_index);
+        q = q_new;
+        push(h, hh);
+    }
+
+    if q != 0. || h_index == 0 {
+        h[h_index] = q;
+        h_index += 1;
+    }
+
+    h_index
+}
+
+fn orient2d_adapt(pa: [f64; 2], pb: [f64; 2], pc: [f64; 2], det_sum: f64) -> f64 {
+    let acx = pa[0] - pc[0];
+    let bcx = pb[0] - pc[0];
+    let acy = pa[1] - pc[1];
+    let bcy = pb[1] - pc[1];
+
+    let (det_left, det_left_tail) = two_product(acx, bcy);
+    let (det_right, det_right_tail) = two_product(acy, bcx);
+
+    let b = two_two_diff(det_left, det_left_tail, det_right, det_right_tail);
+
+    let mut det: f64 = b.iter().sum();
+    let err_bound = CCW_ERR_BOUND_B * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let acx_tail = two_diff_tail(pa[0], pc[0], acx);
+    let bcx_tail = two_diff_tail(pb[0], pc[0], bcx);
+    let acy_tail = two_diff_tail(pa[1], pc[1], acy);
+    let bcy_tail = two_diff_tail(pb[1], pc[1], bcy);
+
+    if acx_tail == 0. && acy_tail == 0. && bcx_tail == 0. && bcy_tail == 0. {
+        return det;
+    }
+
+    let err_bound = CCW_ERR_BOUND_C * det_sum + RESULT_ERR_BOUND * det.abs();
+    det += (acx * bcy_tail + bcy * acx_tail) - (acy * bcx_tail + bcx * acy_tail);
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let cross_expansion = |a0, b0, a1, b1| {
+        let (s1, s0) = two_product(a0, b0);
+        let (t1, t0) = two_product(a1, b1);
+        two_two_diff(s1, s0, t1, t0)
+    };
+
+    let mut c1 = [0.; 8];
+    let u = cross_expansion(acx_tail, bcy, acy_tail, bcx);
+    let c1_len = fast_expansion_sum_zeroelim(&b, &u, &mut c1);
+
+    let mut c2 = [0.; 12];
+    let u = cross_expansion(acx, bcy_tail, acy, bcx_tail);
+    let c2_len = fast_expansion_sum_zeroelim(&c1[..c1_len], &u, &mut c2);
+
+    let mut d = [0.; 16];
+    let u = cross_expansion(acx_tail, bcy_tail, acy_tail, bcx_tail);
+    let d_len = fast_expansion_sum_zeroelim(&c2[..c2_len], &u, &mut d);
+
+    d[d_len - 1]
+}
+
+/// Returns a positive value if the points `pa`, `pb`, and `pc` occur in counterclockwise
+/// order, a negative value if they occur in clockwise order, and zero if they are collinear.
+///
+/// The sign of the returned value is always exact. The value itself is an approximation
+/// of twice the signed area of the triangle `pa, pb, pc`.
+///
+/// The fast floating-point path is taken whenever its error bound proves the sign correct,
+/// the (much slower) exact arithmetic is only used for nearly collinear points.
+#[inline]
+#[must_use]
+pub fn orient2d(pa: [f64; 2], pb: [f64; 2], pc: [f64; 2]) -> f64 {
+    let det_left = (pa[0] - pc[0]) * (pb[1] - pc[1]);
+    let det_right = (pa[1] - pc[1]) * (pb[0] - pc[0]);
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0. {
+        if det_right <= 0. {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0. {
+        if det_right >= 0. {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let err_bound = CCW_ERR_BOUND_A * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    orient2d_adapt(pa, pb, pc, det_sum)
+}
+
+/// Returns whether the line going through `p` and `q` separates the
+/// endpoints of the line segment `[a, b]`, using exact orientation tests.
+///
+/// Endpoints lying exactly on the line are considered to be on it's left (counterclockwise)
+/// side. This half-open convention ensures that, for any closed polyline, a line passing
+/// exactly through a vertex is counted as crossing exactly one of the two segments sharing
+/// that vertex, or none of them, so crossing-count parity is always preserved.
+///
+/// A segment lying entirely on the line is never considered to be crossed.
+///
+/// Only the result is exact: `p` and `q` themselves must already be rounded, (e. g. when
+/// computing `q` as `p + dir`), so the line tested is the one going through those
+/// rounded points. Using the same `p` and `q` for all segments of a polyline is what
+/// guarantees consistent results between them.
+#[inline]
+#[must_use]
+pub fn line_crosses_segment(p: [f64; 2], q: [f64; 2], a: [f64; 2], b: [f64; 2]) -> bool {
+    (orient2d(p, q, a) >= 0.) != (orient2d(p, q, b) >= 0.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// xorshift64, for reproducibility
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// A float in `[-1, 1)`
+        fn float(&mut self) -> f64 {
+            (self.next() >> 11) as f64 / (1u64 << 52) as f64 - 1.
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    /// `orient2d`, computed exactly on points whose coordinates are multiples of `2^-53`,
+    /// with magnitudes below `2^10`.
+    fn orient2d_exact(pa: [f64; 2], pb: [f64; 2], pc: [f64; 2]) -> i32 {
+        let scale = |c: f64| {
+            let scaled = c * (1u64 << 53) as f64;
+            assert_eq!(scaled.fract(), 0.);
+            scaled as i128
+        };
+
+        let [pa, pb, pc] = [pa, pb, pc].map(|p| p.map(scale));
+        let det = (pa[0] - pc[0]) * (pb[1] - pc[1]) - (pa[1] - pc[1]) * (pb[0] - pc[0]);
+        det.signum() as i32
+    }
+
+    fn sign(x: f64) -> i32 {
+        if x > 0. {
+            1
+        } else if x < 0. {
+            -1
+        } else {
+            0
+        }
+    }
+
+    #[test]
+    fn orient2d_nearly_collinear() {
+        // the classic example from Shewchuk's paper: a grid of points, a few ulps
+        // apart, around a point of the line going through `pb` and `pc`
+        let (pb, pc) = ([12., 12.], [24., 24.]);
+        let ulp = f64::EPSILON / 2.;
+
+        for i in 0..64 {
+            for j in 0..64 {
+                let pa = [0.5 + f64::from(i) * ulp, 0.5 + f64::from(j) * ulp];
+                assert_eq!(
+                    sign(orient2d(pa, pb, pc)),
+                    orient2d_exact(pa, pb, pc),
+                    "{pa:?}",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn orient2d_is_antisymmetric() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let ulp = f64::EPSILON / 2.;
+
+        for _ in 0..10_000 {
+            let pb = [rng.float(), rng.float()];
+            let pc = [rng.float(), rng.float()];
+            let t = rng.float();
+            // a point on the line, (after rounding) nudged by a few ulps
+            let pa = [
+                pb[0] + t * (pc[0] - pb[0]) + (rng.below(5) as f64 - 2.) * ulp,
+                pb[1] + t * (pc[1] - pb[1]) + (rng.below(5) as f64 - 2.) * ulp,
+            ];
+
+            let o = sign(orient2d(pa, pb, pc));
+            assert_eq!(o, sign(orient2d(pb, pc, pa)));
+            assert_eq!(o, sign(orient2d(pc, pa, pb)));
+            assert_eq!(o, -sign(orient2d(pb, pa, pc)));
+            assert_eq!(o, -sign(orient2d(pa, pc, pb)));
+        }
+    }
+
+    #[test]
+    fn expansion_sum_is_exact() {
+        let tiny = f64::EPSILON * f64::EPSILON;
+        let mut h = [0.; 4];
+
+        // cancellation leaves a single zero component
+        assert_eq!(fast_expansion_sum_zeroelim(&[1.], &[-1.], &mut h), 1);
+        assert_eq!(h[0], 0.);
+
+        // components too far apart to be added in floating point are kept
+        let len = fast_expansion_sum_zeroelim(&[tiny, 1.], &[tiny * tiny, 2.], &mut h);
+        assert_eq!(&h[..len], &[tiny * tiny, tiny, 3.]);
+
+        // zero components are eliminated
+        let len = fast_expansion_sum_zeroelim(&[tiny, 1.], &[-tiny, 2.], &mut h);
+        assert_eq!(&h[..len], &[3.]);
+    }
+
+    /// Returns the number of segments of the closed polyline going through
+    /// `vertices` that the line going through `p` and `q` crosses.
+    fn crossings(p: [f64; 2], q: [f64; 2], vertices: &[[f64; 2]]) -> usize {
+        let next = vertices.iter().cycle().skip(1);
+
+        vertices
+            .iter()
+            .zip(next)
+            .filter(|(&a, &b)| line_crosses_segment(p, q, a, b))
+            .count()
+    }
+
+    #[test]
+    fn crossing_parity_through_vertices() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..10_000 {
+            let n = 3 + rng.below(8);
+            let mut vertices = [[0.; 2]; 10];
+            vertices[..n]
+                .iter_mut()
+                .for_each(|v| *v = [rng.float(), rng.float()]);
+            let vertices = &vertices[..n];
+
+            // lines going exactly through one or two vertices of the polyline
+            let (i, j) = (rng.below(n), rng.below(n));
+            let p = vertices[i];
+            let q = if i == j {
+                [p[0] + rng.float(), p[1] + rng.float()]
+            } else {
+                vertices[j]
+            };
+
+            assert_eq!(crossings(p, q, vertices) % 2, 0, "{p:?} {q:?} {vertices:?}");
+        }
+    }
+
+    #[test]
+    fn crossing_parity_nearly_collinear() {
+        let mut rng = Rng(0x5851_f42d_4c95_7f2d);
+
+        for _ in 0..10_000 {
+            let p = [rng.float(), rng.float()];
+            let q = [rng.float(), rng.float()];
+
+            // vertices rounded onto (or right next to) the line, with some
+            // of them repeated, so that consecutive segments share them
+            let n = 3 + rng.below(8);
+            let mut vertices = [[0.; 2]; 10];
+            for v in &mut vertices[..n] {
+                let t = 4. * rng.float();
+                *v = [p[0] + t * (q[0] - p[0]), p[1] + t * (q[1] - p[1])];
+            }
+            let vertices = &vertices[..n];
+
+            assert_eq!(crossings(p, q, vertices) % 2, 0, "{p:?} {q:?} {vertices:?}");
+        }
+    }
+
+    #[test]
+    fn half_open_convention() {
+        let (p, q) = ([0., 0.], [1., 0.]);
+
+        // an endpoint on the line counts as being on it's left side
+        assert!(line_crosses_segment(p, q, [0.5, 0.], [0.5, -1.]));
+        assert!(!line_crosses_segment(p, q, [0.5, 0.], [0.5, 1.]));
+
+        // so a polyline touching the line at a vertex crosses it zero or two times
+        let (up, on, down) = ([0., 1.], [0.5, 0.], [1., -1.]);
+        assert_eq!(crossings(p, q, &[up, on, [1., 1.]]), 0);
+        assert_eq!(crossings(p, q, &[down, on, [0., -1.]]), 2);
+        assert_eq!(crossings(p, q, &[up, on, down]), 2);
+
+        // segments lying on the line are never crossed
+        assert!(!line_crosses_segment(p, q, [-1., 0.], [2., 0.]));
+    }
+}
@@ -0,0 +1,233 @@
+use super::*;
+use arrayvec::ArrayVec;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc, vec::Vec};
+
+/// The reason two mirrors couldn't be interpolated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LerpError {
+    /// The mirrors don't have the same structure, (e.g. containers of different lengths,
+    /// or [`Prioritized`] mirrors with different priorities).
+    MismatchedStructure,
+    /// An interpolated shape was rejected by it's constructor, (e.g. a simplex whose
+    /// vertices became affinely dependent, or a sphere whose radius became zero).
+    InvalidShape,
+}
+
+impl fmt::Display for LerpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MismatchedStructure => "mirrors don't have the same structure",
+            Self::InvalidShape => "an interpolated shape is invalid",
+        })
+    }
+}
+
+impl core::error::Error for LerpError {}
+
+/// Returns `a * (1 - t) + b * t`, which is exactly `a` when `t == 0`,
+/// and exactly `b` when `t == 1`.
+#[inline]
+pub(crate) fn lerp<S: RealField>(a: &S, b: &S, t: &S) -> S {
+    a.clone() * (S::one() - t.clone()) + b.clone() * t.clone()
+}
+
+/// Like [`lerp`], but component-wise.
+#[inline]
+pub(crate) fn lerp_vector<S: RealField, const D: usize>(
+    a: &SVector<S, D>,
+    b: &SVector<S, D>,
+    t: &S,
+) -> SVector<S, D> {
+    a.zip_map(b, |a, b| lerp(&a, &b, t))
+}
+
+/// A trait for mirrors whose parameters (positions, radii, etc...) can be interpolated,
+/// e. g. to animate transitions between scenes.
+///
+/// Leaves (shapes) interpolate their parameters linearly, and validate the resulting shape
+/// like their `try_new` constructor does. Containers interpolate their elements pairwise,
+/// and require both mirrors to have the same length.
+///
+/// ```
+/// use miroir::nalgebra::Vector2;
+/// use miroir_shapes::{LerpError, LerpParams, LineSegment, Sphere};
+///
+/// let circle = Sphere::new([0., 0.], 1.);
+///
+/// let a = (circle, [LineSegment::new([[-1., -2.], [1., -2.]])]);
+/// let b = (circle, [LineSegment::new([[-1., 2.], [1., 2.]])]);
+///
+/// // the segment's midpoint sweeps the center of the circle
+/// let (_, [segment]) = a.lerp_params(&b, 0.5).unwrap();
+/// assert_eq!(segment.vertices(), [Vector2::new(-1., 0.), Vector2::new(1., 0.)]);
+///
+/// // collinear endpoints don't form a valid segment
+/// let c = (circle, [LineSegment::new([[1., -2.], [-1., -2.]])]);
+/// assert_eq!(a.lerp_params(&c, 0.5), Err(LerpError::InvalidShape));
+/// ```
+pub trait LerpParams<S>: Sized {
+    /// Returns the mirror whose parameters are those of `self` and `other`, linearly
+    /// interpolated by `t`: `t == 0` yields `self`'s parameters, and `t == 1`, `other`'s.
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError>;
+}
+
+use impl_trait_for_tuples::impl_for_tuples;
+
+#[impl_for_tuples(1, 16)]
+impl<S: Clone> LerpParams<S> for T {
+    for_tuples!( where #( T: LerpParams<S> )* );
+
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        Ok(for_tuples!( ( #( self.T.lerp_params(&other.T, t.clone())? ),* ) ))
+    }
+}
+
+impl<const N: usize, S: Clone, T: LerpParams<S>> LerpParams<S> for [T; N] {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        let mirrors = self
+            .iter()
+            .zip(other)
+            .map(|(a, b)| a.lerp_params(b, t.clone()))
+            .collect::<Result<ArrayVec<_, N>, _>>()?;
+
+        match mirrors.into_inner() {
+            Ok(mirrors) => Ok(mirrors),
+            Err(_) => unreachable!("both arrays have N elements"),
+        }
+    }
+}
+
+impl<S, T: LerpParams<S>> LerpParams<S> for Prioritized<T> {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        if self.1 != other.1 {
+            return Err(LerpError::MismatchedStructure);
+        }
+
+        self.0
+            .lerp_params(&other.0, t)
+            .map(|mirror| Self(mirror, self.1))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: LerpParams<S>> LerpParams<S> for Box<T> {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        (**self).lerp_params(other, t).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: LerpParams<S>> LerpParams<S> for Arc<T> {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        (**self).lerp_params(other, t).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: LerpParams<S>> LerpParams<S> for Rc<T> {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        (**self).lerp_params(other, t).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Clone, T: LerpParams<S>> LerpParams<S> for Vec<T> {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        if self.len() != other.len() {
+            return Err(LerpError::MismatchedStructure);
+        }
+
+        self.iter()
+            .zip(other)
+            .map(|(a, b)| a.lerp_params(b, t.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    #[test]
+    fn lerping_yields_the_endpoints_exactly() {
+        let a = (
+            Sphere::<f64, 2>::new([0.3, -0.2], 1.5),
+            [LineSegment::new([[-1., 0.5], [2., -0.25]])],
+            Prioritized(LineSegment::new([[0.1, 0.2], [0.3, 0.7]]), 1),
+        );
+        let b = (
+            Sphere::<f64, 2>::new([-1.1, 0.7], 0.3),
+            [LineSegment::new([[0.4, 1.9], [-3., 0.15]])],
+            Prioritized(LineSegment::new([[-0.1, 0.2], [1e-3, 0.6]]), 1),
+        );
+
+        assert_eq!(a.lerp_params(&b, 0.), Ok(a.clone()));
+        assert_eq!(a.lerp_params(&b, 1.), Ok(b.clone()));
+
+        let a = Cylinder::<f64>::new([0., 0., -1.], [0.2, 0.1, 1.], 0.5);
+        let b = Cylinder::<f64>::new([1., 0.3, -1.], [-0.2, 0.1, 2.], 0.25);
+
+        assert_eq!(a.lerp_params(&b, 0.), Ok(a.clone()));
+        assert_eq!(a.lerp_params(&b, 1.), Ok(b.clone()));
+    }
+
+    #[test]
+    fn lerping_is_monotonic() {
+        let a = Sphere::<f64, 2>::new([-1., 2.], 0.5);
+        let b = Sphere::<f64, 2>::new([3., -2.], 2.);
+
+        let spheres = (0..=100)
+            .map(|i| a.lerp_params(&b, f64::from(i) / 100.).unwrap())
+            .collect::<std::vec::Vec<_>>();
+
+        for pair in spheres.windows(2) {
+            assert!(pair[0].center.x < pair[1].center.x);
+            assert!(pair[0].center.y > pair[1].center.y);
+            assert!(pair[0].radius() < pair[1].radius());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn lerping_different_structures_fails() {
+        let segment = LineSegment::<f64>::new([[0., 0.], [1., 0.]]);
+
+        let a = std::vec![segment.clone()];
+        let b = std::vec![segment.clone(); 2];
+        assert_eq!(a.lerp_params(&b, 0.5), Err(LerpError::MismatchedStructure));
+
+        let (a, b) = (Prioritized(segment.clone(), 0), Prioritized(segment, 1));
+        assert_eq!(a.lerp_params(&b, 0.5), Err(LerpError::MismatchedStructure));
+    }
+
+    #[test]
+    fn lerping_to_degenerate_shapes_fails() {
+        // the radius shrinks to zero
+        let a = Sphere::<f64, 2>::new([0., 0.], 1.);
+        let b = Sphere::<f64, 2>::new([0., 0.], 0.);
+        assert!(a.lerp_params(&b, 0.5).is_ok());
+        assert_eq!(a.lerp_params(&b, 1.), Err(LerpError::InvalidShape));
+
+        // the segment is flipped, through a single point
+        let a = LineSegment::<f64>::new([[-1., 0.], [1., 0.]]);
+        let b = LineSegment::<f64>::new([[1., 0.], [-1., 0.]]);
+        assert_eq!(a.lerp_params(&b, 0.5), Err(LerpError::InvalidShape));
+
+        // the cylinder's axis shrinks to a point
+        let a = Cylinder::<f64>::new([0., 0., -1.], [0., 0., 1.], 0.5);
+        let b = Cylinder::<f64>::new([0., 0., 1.], [0., 0., -1.], 0.5);
+        assert_eq!(a.lerp_params(&b, 0.5), Err(LerpError::InvalidShape));
+    }
+}
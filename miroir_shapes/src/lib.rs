@@ -1,13 +1,51 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(test)]
+mod conformance_tests;
 mod cylinder;
+#[cfg(feature = "exact2d")]
+mod exact2d;
+mod lerp;
 mod simplex;
 mod sphere;
+mod symmetry;
+#[cfg(test)]
+mod test_util;
+mod visit;
 
 pub use cylinder::*;
+#[cfg(feature = "exact2d")]
+pub use exact2d::*;
+pub use lerp::*;
 pub use simplex::*;
 pub use sphere::*;
+pub use symmetry::*;
+pub use visit::*;
 
 use miroir::*;
 
-use nalgebra::{SVector, Unit};
+use nalgebra::{RealField, SVector, Unit};
+
+/// How many times larger than the precision of it's scalar type (relative to the
+/// magnitude of it's position) the radius of a curved shape must at least be.
+///
+/// Smaller radii make the normals computed at intersection points inaccurate enough
+/// that they can't be considered unit vectors anymore.
+///
+/// Normals are divided by the radius, so their norm is off by the rounding error of the
+/// intersection point, relative to the radius. For cylinders, that error grows with the
+/// distance to the axis' start, and more so for rays grazing them, hence the large margin.
+/// This rejects radii below `1/16` times the magnitude of the shape's position for `f32`,
+/// and about `1.2e-10` times it for `f64`.
+pub const MIN_RELATIVE_RADIUS_ULPS: f64 = 524_288.;
+
+/// Whether `radius` is large enough, compared to `magnitude`, the norm of
+/// a point of a shape, for normals computed on that shape to be accurate.
+#[inline]
+fn is_valid_radius<S: RealField>(radius: &S, magnitude: S) -> bool {
+    let min = magnitude * S::default_epsilon() * S::from_subset(&MIN_RELATIVE_RADIUS_ULPS);
+    radius.is_finite() && *radius > S::zero() && *radius >= min
+}
@@ -1,4 +1,4 @@
-use core::ops::AddAssign;
+use core::{array, ops::AddAssign};
 
 use nalgebra::{ComplexField, RealField};
 
@@ -12,6 +12,11 @@ pub struct Simplex<S, const D: usize> {
     plane: HyperplaneBasis<S, D>,
     /// The same plane, but represented with an orthonormal basis, useful for orthogonal symmetries
     orthonormalised: HyperplaneBasisOrtho<S, D>,
+    /// The exact points this simplex was constructed with. `vertices()` recomputes them
+    /// with roundoff errors, which would make adjacent segments disagree on shared vertices.
+    /// `None` once the plane is modified through [`Self::inner_plane_mut`].
+    #[cfg(feature = "exact2d")]
+    points: Option<[SVector<S, D>; D]>,
 }
 
 pub type Triangle<S> = Simplex<S, 3>;
@@ -28,6 +33,8 @@ impl<S: ComplexField, const D: usize> Simplex<S, D> {
     #[inline]
     pub fn try_new(points: [impl Into<SVector<S, D>>; D]) -> Option<Self> {
         let mut vectors: [SVector<_, D>; D] = points.map(Into::into);
+        #[cfg(feature = "exact2d")]
+        let points = Some(vectors.clone());
         let (v0, basis) = vectors.split_first_mut().unwrap();
 
         basis.iter_mut().for_each(|v| *v -= v0.clone());
@@ -35,6 +42,8 @@ impl<S: ComplexField, const D: usize> Simplex<S, D> {
         HyperplaneBasis::try_new(vectors).map(|(plane, orthonormalised)| Self {
             plane,
             orthonormalised,
+            #[cfg(feature = "exact2d")]
+            points,
         })
     }
 
@@ -49,6 +58,22 @@ impl<S: ComplexField, const D: usize> Simplex<S, D> {
     }
 }
 
+impl<S: RealField> LineSegment<S> {
+    /// Creates a line segment of length `length`, centered at `center`, whose direction
+    /// makes an angle of `theta` with the x axis (see [`Vector2Ext`]).
+    ///
+    /// # Panics
+    ///
+    /// if `length` is zero
+    #[inline]
+    #[must_use]
+    pub fn from_center_angle_length(center: impl Into<SVector<S, 2>>, theta: S, length: S) -> Self {
+        let center = center.into();
+        let half = SVector::from_angle(theta) * (length / S::from_subset(&2.));
+        Self::new([&center - &half, center + half])
+    }
+}
+
 impl<S, const D: usize> Simplex<S, D> {
     #[inline]
     #[must_use]
@@ -62,9 +87,16 @@ impl<S, const D: usize> Simplex<S, D> {
     /// ```
     ///
     /// Effectively translates this whole simplex.
+    ///
+    /// With the `exact2d` feature enabled, line segments modified this way no longer
+    /// use exact predicates for intersection tests.
     #[inline]
     #[must_use]
     pub fn inner_plane_mut(&mut self) -> &mut HyperplaneBasis<S, D> {
+        #[cfg(feature = "exact2d")]
+        {
+            self.points = None;
+        }
         &mut self.plane
     }
 
@@ -75,6 +107,67 @@ impl<S, const D: usize> Simplex<S, D> {
     }
 }
 
+impl<S: RealField, const D: usize> LerpParams<S> for Simplex<S, D> {
+    /// The first vertex, and the edges starting from it, are interpolated, then validated
+    /// like [`Self::try_new`] does.
+    ///
+    /// With the `exact2d` feature enabled, the exact points are interpolated instead, and the
+    /// simplex is rebuilt from them with [`Self::try_new`], so it still uses exact predicates.
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        #[cfg(feature = "exact2d")]
+        if let (Some(a), Some(b)) = (&self.points, &other.points) {
+            let points = array::from_fn(|i| lerp_vector(&a[i], &b[i], &t));
+            return Self::try_new(points).ok_or(LerpError::InvalidShape);
+        }
+
+        let (a, b) = (self.plane.vectors_raw(), other.plane.vectors_raw());
+        let vectors = array::from_fn(|i| lerp_vector(&a[i], &b[i], &t));
+
+        let (plane, orthonormalised) =
+            HyperplaneBasis::try_new(vectors).ok_or(LerpError::InvalidShape)?;
+
+        Ok(Self {
+            plane,
+            orthonormalised,
+            #[cfg(feature = "exact2d")]
+            points: None,
+        })
+    }
+}
+
+impl<S: ComplexField, const D: usize> ReflectAcross<S, D> for Simplex<S, D> {
+    /// The first vertex is reflected as a point, and the edges starting from it, as vectors,
+    /// so that, for axis-aligned `plane`s, the reflected simplex is exactly symmetric to `self`.
+    ///
+    /// With the `exact2d` feature enabled, the exact points are reflected instead, and the
+    /// simplex is rebuilt from them with [`Self::try_new`], so it still uses exact predicates.
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        #[cfg(feature = "exact2d")]
+        if let Some(points) = &self.points {
+            let points = points.each_ref().map(|p| reflect_point_across(p, plane));
+            return Self::try_new(points);
+        }
+
+        let mut vectors = self.plane.vectors_raw().clone();
+        let (v0, basis) = vectors.split_first_mut().unwrap();
+
+        *v0 = reflect_point_across(v0, plane);
+        basis
+            .iter_mut()
+            .for_each(|v| *v = reflect_vector_across(v, plane));
+
+        // orthogonal symmetries preserve linear independence, but rounding might not
+        HyperplaneBasis::try_new(vectors).map(|(plane_basis, orthonormalised)| Self {
+            plane: plane_basis,
+            orthonormalised,
+            #[cfg(feature = "exact2d")]
+            points: None,
+        })
+    }
+}
+
 impl<S: ComplexField, const D: usize, U> TryFrom<[U; D]> for Simplex<S, D>
 where
     SVector<S, D>: From<U>,
@@ -108,8 +201,25 @@ where
 
 impl<S: RealField, const D: usize> Simplex<S, D> {
     /// Returns the distance `d` such that [`ray.at(d)`](Ray::at) intersects with `self`
+    ///
+    /// With the `exact2d` feature enabled, whether a line segment is hit
+    /// is decided using [`line_crosses_segment`] instead. The returned distance is still
+    /// computed in floating point, and can thus be slightly off, (e. g. negative for a
+    /// ray starting on the segment). Segments parallel to a ray (in floating point), but
+    /// still crossed by it's line, are hit where the predicates place the crossing.
     #[inline]
     pub fn intersection(&self, ray: &Ray<S, D>) -> Option<S> {
+        #[cfg(feature = "exact2d")]
+        if let Some(hit) = self.intersection_exact_2d(ray) {
+            return hit;
+        }
+
+        self.intersection_inexact(ray)
+    }
+
+    /// [`Self::intersection`], computed in floating point only.
+    #[inline]
+    fn intersection_inexact(&self, ray: &Ray<S, D>) -> Option<S> {
         let p = self.inner_plane();
 
         let intersection_coords = p.intersection_coordinates(ray, p.v0());
@@ -133,11 +243,284 @@ impl<S: RealField, const D: usize> Simplex<S, D> {
     }
 }
 
+#[cfg(feature = "exact2d")]
+impl<S: RealField, const D: usize> Simplex<S, D> {
+    /// Returns `None` if exact predicates can't be used for `self` (`D != 2`, the points
+    /// aren't representable as `f64`s or the simplex was modified after its construction).
+    fn intersection_exact_2d(&self, ray: &Ray<S, D>) -> Option<Option<S>> {
+        if D != 2 {
+            return None;
+        }
+
+        let points = self.points.as_ref()?;
+
+        let to_f64 = |v: &SVector<S, D>| -> Option<[f64; 2]> {
+            Some([
+                nalgebra::try_convert_ref(&v[0])?,
+                nalgebra::try_convert_ref(&v[1])?,
+            ])
+        };
+
+        let [a, b] = [to_f64(&points[0])?, to_f64(&points[1])?];
+        let p = to_f64(&ray.origin)?;
+        let [dx, dy] = to_f64(ray.dir.as_ref())?;
+        let q = [p[0] + dx, p[1] + dy];
+
+        if !line_crosses_segment(p, q, a, b) {
+            return Some(None);
+        }
+
+        let plane = self.inner_plane();
+
+        let dist = plane.intersection_coordinates(ray, plane.v0()).map_or_else(
+            || {
+                // the ray is parallel to the segment in floating point, but it's line still
+                // crosses it, where the areas it spans with either endpoint cancel out
+                let (area_a, area_b) = (orient2d(p, q, a), orient2d(p, q, b));
+                let t = S::from_subset(&(area_a / (area_a - area_b)));
+                let crossing = &points[0] + (&points[1] - &points[0]) * t;
+                (crossing - &ray.origin).dot(ray.dir.as_ref())
+            },
+            |v| v[0].clone(),
+        );
+
+        Some(Some(dist))
+    }
+}
+
 impl<S: RealField, const D: usize> Mirror<D> for Simplex<S, D> {
     type Scalar = S;
     fn add_tangents(&self, ctx: &mut SimulationCtx<Self::Scalar, D>) {
         if let Some(t) = self.intersection(ctx.ray()) {
-            ctx.add_tangent(t, Hyperplane::Plane(self.inner_plane_ortho().clone()));
+            let tangent = Hyperplane::Plane(self.inner_plane_ortho().clone());
+            let condition = tangent.incidence_condition(&ctx.ray().dir);
+            ctx.add_tangent_with_condition(t, tangent, condition);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "exact2d"))]
+mod exact2d_tests {
+    use super::*;
+    use crate::test_util::Rng;
+    use core::f64::consts::{PI, TAU};
+
+    fn segments<const N: usize>(vertices: [[f64; 2]; N]) -> [LineSegment<f64>; N] {
+        array::from_fn(|i| LineSegment::new([vertices[i], vertices[(i + 1) % N]]))
+    }
+
+    fn hits(ray: &Ray<f64, 2>, segments: &[LineSegment<f64>]) -> usize {
+        segments
+            .iter()
+            .filter(|s| s.intersection(ray).is_some())
+            .count()
+    }
+
+    #[test]
+    fn rays_through_shared_vertices_hit_an_even_number_of_segments() {
+        // a star shaped polygon, with vertices of all sorts of magnitudes
+        let vertices = [
+            [0.1, 0.7],
+            [0.3, 0.30000000000000004],
+            [1e8, 0.1],
+            [0.2, -0.2],
+            [1. / 3., -1e-9],
+            [-0.7, -0.1],
+            [-0.3, 0.3],
+        ];
+        let walls = segments(vertices);
+
+        for (i, &from) in vertices.iter().enumerate() {
+            for (j, &to) in vertices.iter().enumerate() {
+                if i != j {
+                    let dir = [to[0] - from[0], to[1] - from[1]];
+                    let ray = Ray::new(from, dir);
+                    assert_eq!(hits(&ray, &walls) % 2, 0, "{from:?} -> {to:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn grazing_rays_hit_an_even_number_of_segments() {
+        // a thin sliver, with two vertices a few ulps from the x axis
+        let eps = f64::EPSILON;
+        let walls = segments([[-1., 0.], [0., eps], [1., 0.], [0., -eps]]);
+
+        for dy in [-2. * eps, -eps, -eps / 2., 0., eps / 2., eps, 2. * eps] {
+            for dir in [[1., 0.], [1., eps], [1., -eps], [-1., 0.]] {
+                let ray = Ray::new([-2., dy], dir);
+                assert_eq!(hits(&ray, &walls) % 2, 0, "{dy} {dir:?}");
+            }
+        }
+    }
+
+    /// Counts the segments of `walls` hit in front of `ray`'s origin, using `intersection`.
+    fn hits_in_front(
+        ray: &Ray<f64, 2>,
+        walls: &[LineSegment<f64>],
+        intersection: impl Fn(&LineSegment<f64>, &Ray<f64, 2>) -> Option<f64>,
+    ) -> usize {
+        walls
+            .iter()
+            .filter(|s| intersection(s, ray).is_some_and(|d| d > 0.))
+            .count()
+    }
+
+    #[test]
+    fn rays_through_vertices_have_the_right_crossing_parity() {
+        const N: usize = 7;
+
+        let mut rng = Rng::new(0xDA94_2042_E4DD_58B5);
+        let mut inexact_failures = 0;
+
+        for _ in 0..200 {
+            // a convex polygon, with vertices on the unit circle
+            let vertices: [[f64; 2]; N] = array::from_fn(|i| {
+                let theta = TAU * (i as f64 + 0.4 * (rng.float_f64() + 1.)) / N as f64;
+                [theta.cos(), theta.sin()]
+            });
+            let walls = segments(vertices);
+
+            // a point strictly inside it (a convex combination of the vertices)
+            let weights: [f64; N] = array::from_fn(|_| rng.float_f64() + 1.5);
+            let total: f64 = weights.iter().sum();
+            let inside = vertices
+                .iter()
+                .zip(weights)
+                .fold([0., 0.], |[x, y], (v, w)| {
+                    [x + v[0] * w / total, y + v[1] * w / total]
+                });
+
+            // and one outside it
+            let (r, theta) = (2. + rng.float_f64(), PI * rng.float_f64());
+            let outside = [r * theta.cos(), r * theta.sin()];
+
+            for (origin, parity) in [(inside, 1), (outside, 0)] {
+                for to in vertices {
+                    let ray = Ray::new(origin, [to[0] - origin[0], to[1] - origin[1]]);
+
+                    let exact = hits_in_front(&ray, &walls, LineSegment::intersection);
+                    assert_eq!(exact % 2, parity, "{vertices:?} {origin:?} -> {to:?}");
+
+                    let inexact = hits_in_front(&ray, &walls, LineSegment::intersection_inexact);
+                    if inexact % 2 != parity {
+                        inexact_failures += 1;
+                    }
+                }
+            }
+        }
+
+        // shared vertices are missed, or hit twice, in floating point
+        assert!(inexact_failures > 0);
+    }
+
+    #[test]
+    fn rays_along_segments_hit_them_at_their_origin() {
+        let mut rng = Rng::new(0x2545_F491_4F6C_DD1D);
+        let mut parallel = 0;
+
+        for _ in 0..1000 {
+            let a = [rng.float_f64(), rng.float_f64()];
+            let b = [rng.float_f64(), rng.float_f64()];
+            let segment = LineSegment::new([a, b]);
+
+            // from one endpoint towards the other, parallel to the segment in floating point,
+            // but not always exactly
+            let ray = Ray::new(a, [b[0] - a[0], b[1] - a[1]]);
+
+            if segment.intersection_inexact(&ray).is_none() {
+                parallel += 1;
+
+                if let Some(d) = segment.intersection(&ray) {
+                    assert_eq!(d, 0., "{a:?} {b:?}");
+                }
+            }
+        }
+
+        assert!(parallel > 0);
+    }
+
+    #[test]
+    fn reflected_segments_keep_using_exact_predicates() {
+        // the diagonal `y = x`, not orthogonal to an axis, so the reflection rounds
+        let (_, diagonal) = HyperplaneBasis::try_new([[0., 0.].into(), [1., 1.].into()]).unwrap();
+
+        let segment = LineSegment::new([[0.3, -1.7], [2.1, 0.9]]);
+        let reflected = segment.reflect_across(&diagonal).unwrap();
+
+        assert!(reflected.points.is_some());
+    }
+
+    #[test]
+    fn lerped_segments_keep_using_exact_predicates() {
+        let a = LineSegment::new([[0.3, -1.7], [2.1, 0.9]]);
+        let b = LineSegment::new([[-0.1, 0.7], [1e-3, 0.6]]);
+
+        for t in [0., 0.1, 1. / 3., 0.5, 0.9, 1.] {
+            assert!(a.lerp_params(&b, t).unwrap().points.is_some(), "{t}");
+        }
+    }
+
+    #[test]
+    fn modified_segments_fall_back_to_floating_point() {
+        let mut segment = LineSegment::new([[0., -1.], [0., 1.]]);
+        *segment.inner_plane_mut().v0_mut() += SVector::from([1., 0.]);
+
+        // the exact points are stale, the plane's position is used instead
+        let ray = Ray::new([-1., 0.], [1., 0.]);
+        assert_eq!(segment.intersection(&ray), Some(2.));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conditions() {
+        let segment = LineSegment::<f64>::new([[1., -1.], [1., 1.]]);
+        let condition = |ray: Ray<f64, 2>| {
+            let (_, _, condition) = ray
+                .closest_intersection_with_condition(&segment, 1e-9)
+                .unwrap();
+            condition
+        };
+
+        let head_on = condition(Ray::new([0., 0.], [1., 0.]));
+        assert!((head_on - 1.).abs() < 1e-12);
+
+        // almost parallel to the segment
+        let grazing = condition(Ray::new([1. - 1e-7, -0.5], [1e-6, 1.]));
+        assert!(grazing > 1e5);
+    }
+
+    #[test]
+    fn segments_behind_rays_are_counted() {
+        let segment = LineSegment::<f64>::new([[1., -1.], [1., 1.]]);
+
+        // pointing away from the segment, it's only intersected behind the ray's origin
+        let away = Ray::new([0., 0.], [-1., 0.]);
+        let (hit, behind) = away.closest_intersection_counting_behind(&segment, 1e-9);
+        assert!(hit.is_none());
+        assert_eq!(behind, 1);
+
+        // pointing towards it, nothing is behind the ray's origin
+        let towards = Ray::new([0., 0.], [1., 0.]);
+        let (hit, behind) = towards.closest_intersection_counting_behind(&segment, 1e-9);
+        assert_eq!(behind, 0);
+        assert!((hit.unwrap().0 - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn empty_scenes_have_nothing_behind() {
+        let scene: [LineSegment<f64>; 0] = [];
+
+        for dir in [[1., 0.], [-1., 0.], [0., 1.]] {
+            let ray = Ray::new([0., 0.], dir);
+            let (hit, behind) = ray.closest_intersection_counting_behind(&scene, 1e-9);
+            assert!(hit.is_none());
+            assert_eq!(behind, 0);
         }
     }
 }
@@ -1,4 +1,4 @@
-use nalgebra::ComplexField;
+use nalgebra::{ComplexField, RealField};
 
 use super::*;
 
@@ -24,12 +24,26 @@ impl<S: ComplexField, const D: usize> Sphere<S, D> {
         }
     }
 
+    /// Like [`Self::new`], but returns `None` if `radius` is too small, relative to
+    /// the norm of `center`, for the normals computed on this sphere to be accurate
+    /// (see [`MIN_RELATIVE_RADIUS_ULPS`]), or zero, or not finite.
+    #[inline]
+    #[must_use]
+    pub fn try_new(
+        center: impl Into<SVector<S, D>>,
+        radius: impl Into<S::RealField>,
+    ) -> Option<Self> {
+        let sphere = Self::new(center, radius);
+        is_valid_radius(sphere.radius(), sphere.center.norm()).then_some(sphere)
+    }
+
     #[inline]
     #[must_use]
     pub fn radius(&self) -> &S::RealField {
         &self.radius
     }
 
+    /// Note that no validation is done on `r`, see [`Self::try_new`].
     #[inline]
     pub fn set_radius(&mut self, r: S::RealField) {
         self.radius = r.clone().abs();
@@ -63,12 +77,36 @@ impl<S: ComplexField, const D: usize> Sphere<S, D> {
         &self,
         ray: &Ray<S, D>,
     ) -> Option<[(S, Unit<SVector<S, D>>); 2]> {
-        self.intersections(ray).map(|ds| ds.map(|d| (
-            d.clone(),
+        self.intersections(ray).map(|ds| ds.map(|d| {
             // SAFETY: p := ray.at(d) is in the sphere,
             // so ||p - self.center|| = |self.radius|
-            Unit::new_unchecked((ray.at(d) - self.center.clone()).unscale(self.radius.clone().abs())),
-        )))
+            let n = (ray.at(d.clone()) - self.center.clone()).unscale(self.radius.clone().abs());
+            debug_assert_unit!(n, self);
+            (d, Unit::new_unchecked(n))
+        }))
+    }
+}
+
+impl<S: RealField, const D: usize> LerpParams<S> for Sphere<S, D> {
+    #[inline]
+    fn lerp_params(&self, other: &Self, t: S) -> Result<Self, LerpError> {
+        Self::try_new(
+            lerp_vector(&self.center, &other.center, &t),
+            lerp(self.radius(), other.radius(), &t),
+        )
+        .ok_or(LerpError::InvalidShape)
+    }
+}
+
+impl<S: ComplexField, const D: usize> ReflectAcross<S, D> for Sphere<S, D> {
+    /// The reflected sphere is validated like [`Self::try_new`] does, since it's center may
+    /// be farther from the origin.
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        Self::try_new(
+            reflect_point_across(&self.center, plane),
+            self.radius().clone(),
+        )
     }
 }
 
@@ -77,8 +115,132 @@ impl<S: ComplexField, const D: usize> Mirror<D> for Sphere<S, D> {
     fn add_tangents(&self, ctx: &mut SimulationCtx<Self::Scalar, D>) {
         if let Some(tangents) = self.tangents_at_intersections(ctx.ray()) {
             for (d, n) in tangents {
-                ctx.add_tangent(d, Hyperplane::Normal(n));
+                let tangent = Hyperplane::Normal(n);
+                let condition = tangent.incidence_condition(&ctx.ray().dir);
+                ctx.add_tangent_with_condition(d, tangent, condition);
             }
         }
     }
 }
+
+impl<S: RealField> Sphere<S, 2> {
+    /// The point of this circle at an angle of `theta` from the x axis,
+    /// relative to it's center (see [`Vector2Ext`]).
+    #[inline]
+    #[must_use]
+    pub fn point_at_angle(&self, theta: S) -> SVector<S, 2> {
+        &self.center + SVector::from_angle(theta) * self.radius.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_util::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn conditions() {
+        let circle = Sphere::<f64, 2>::new([0., 0.], 1.);
+        let condition = |ray: Ray<f64, 2>| {
+            let (_, _, condition) = ray
+                .closest_intersection_with_condition(&circle, 1e-9)
+                .unwrap();
+            condition
+        };
+
+        let head_on = condition(Ray::new([-3., 0.], [1., 0.]));
+        assert!((head_on - 1.).abs() < 1e-12);
+
+        // tangent to the circle at `y = 1`
+        let grazing = condition(Ray::new([-3., 1. - 1e-8], [1., 0.]));
+        assert!(grazing > 1e3);
+    }
+
+    #[test]
+    fn degenerate_spheres_are_rejected() {
+        for radius in [0., -0., f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(Sphere::<f64, 3>::try_new([1., 2., 3.], radius).is_none());
+        }
+
+        assert!(Sphere::<f64, 2>::try_new([f64::NAN, 0.], 1.).is_none());
+        assert!(Sphere::<f64, 2>::try_new([f64::INFINITY, 0.], 1.).is_none());
+
+        // the center is so far away, that the sphere is a single point, at `f32` precision
+        assert!(Sphere::<f32, 2>::try_new([1e6, 0.], 1e-3).is_none());
+        assert!(Sphere::<f32, 2>::try_new([1e3, 0.], min_radius_f32(1e3) * 0.9).is_none());
+    }
+
+    #[test]
+    fn small_f32_radii_are_accepted() {
+        assert!(Sphere::<f32, 3>::try_new([1., 0., 0.], 0.1).is_some());
+        assert!(Sphere::<f32, 2>::try_new([1e3, 0.], min_radius_f32(1e3)).is_some());
+        // negative radii are made positive
+        assert!(Sphere::<f32, 3>::try_new([0., 0., 0.], -1.).is_some());
+    }
+
+    /// Spheres accepted by `try_new`, with radii as small as possible, far from the origin,
+    /// never compute normals tripping `debug_assert_unit`.
+    #[test]
+    #[cfg(debug_assertions)]
+    fn accepted_spheres_compute_unit_normals() {
+        use nalgebra::Vector3;
+
+        let mut rng = Rng::new(0x9E37_79B9_7F4A_7C15);
+        let mut accepted = 0;
+
+        for _ in 0..10_000 {
+            let scale = 10f32.powf(rng.float() * 4.);
+            let center = Vector3::new(rng.float(), rng.float(), rng.float()) * scale;
+
+            let radius = min_radius_f32(center.norm()) * (1. + 4. * (rng.float() + 1.));
+
+            // rays starting around the sphere, pointing towards it's center
+            let offset = Vector3::new(rng.float(), rng.float(), rng.float()) * radius * 4.;
+            let Some(ray) = Ray::try_new(center + offset, -offset) else {
+                continue;
+            };
+
+            if let Some(sphere) = Sphere::<f32, 3>::try_new(center, radius) {
+                accepted += 1;
+                let _ = sphere.tangents_at_intersections(&ray);
+            }
+        }
+
+        assert!(accepted > 5_000, "{accepted}");
+    }
+
+    #[test]
+    fn near_tangent_chords_under_each_policy() {
+        let eps = 1e-3;
+
+        // a tiny tilted segment, right below the top of a unit circle, turns a vertical ray
+        // into a horizontal one, along a chord `2.8e-4` long, shorter than `eps`
+        let y = 1. - 1e-8;
+        let scene = (
+            Sphere::<f64, 2>::new([0., 0.], 1.),
+            LineSegment::new([[-1e-5, y - 1e-5], [1e-5, y + 1e-5]]),
+        );
+        let ray = Ray::new([0., 0.], [0., 1.]);
+
+        let path = |policy| {
+            RayPath::new(ray.clone(), &scene, eps)
+                .with_policy(policy)
+                .take(20)
+                .collect::<Vec<_>>()
+        };
+
+        // the end of the chord is skipped, the ray tunnels out of the circle
+        let eps_travel = path(ExclusionPolicy::EpsTravel);
+        assert_eq!(eps_travel.len(), 1);
+        assert!((eps_travel[0].dir.x - 1.).abs() < 1e-12);
+
+        // the ray is moved `eps` away from the segment, below the chord, and stays trapped
+        let normal_offset = path(ExclusionPolicy::NormalOffset);
+        assert_eq!(normal_offset.len(), 20);
+        assert!((normal_offset[1].origin.norm() - 1.).abs() < 1e-9);
+        assert!(normal_offset.iter().all(|r| r.origin.norm() < 1. + 1e-9));
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+use arrayvec::ArrayVec;
+use nalgebra::ComplexField;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc, vec::Vec};
+
+/// Returns the index of the axis `plane` is orthogonal to, if any, i. e. the coordinate
+/// all vectors of it's basis have exactly equal to zero.
+#[inline]
+fn normal_axis<S: ComplexField, const D: usize>(
+    plane: &HyperplaneBasisOrtho<S, D>,
+) -> Option<usize> {
+    (0..D).find(|&k| plane.basis().iter().all(|e| e[k].is_zero()))
+}
+
+/// Returns the image of the vector `v` by the orthogonal symmetry w.r.t. the direction
+/// space of `plane`.
+///
+/// If `plane` is orthogonal to one of the axes, the corresponding coordinate of `v` is
+/// simply negated, exactly.
+#[inline]
+#[must_use]
+pub fn reflect_vector_across<S: ComplexField, const D: usize>(
+    v: &SVector<S, D>,
+    plane: &HyperplaneBasisOrtho<S, D>,
+) -> SVector<S, D> {
+    if let Some(k) = normal_axis(plane) {
+        let mut v = v.clone();
+        v[k] = -v[k].clone();
+        v
+    } else {
+        let projection = plane.project(v);
+        &projection + &projection - v
+    }
+}
+
+/// Returns the image of the point `p` by the orthogonal symmetry w.r.t. the affine
+/// hyperplane passing through [`plane.v0()`](HyperplaneBasis::v0), and directed by `plane`.
+///
+/// If `plane` is orthogonal to one of the axes, only the corresponding coordinate of `p` is
+/// changed, (exactly negated if `plane` also passes through the origin).
+#[inline]
+#[must_use]
+pub fn reflect_point_across<S: ComplexField, const D: usize>(
+    p: &SVector<S, D>,
+    plane: &HyperplaneBasisOrtho<S, D>,
+) -> SVector<S, D> {
+    let v0 = plane.v0();
+
+    if let Some(k) = normal_axis(plane) {
+        let mut p = p.clone();
+        let offset = p[k].clone() - v0[k].clone();
+        p[k] = v0[k].clone() - offset;
+        p
+    } else {
+        v0 + reflect_vector_across(&(p - v0), plane)
+    }
+}
+
+/// A trait for mirrors whose image by an orthogonal symmetry can be computed.
+///
+/// Useful for building symmetric scenes without typing (and rounding) the coordinates of
+/// both halves by hand, see [`mirrored`].
+pub trait ReflectAcross<S: ComplexField, const D: usize>: Sized {
+    /// Returns the image of `self` by the orthogonal symmetry w.r.t. the affine hyperplane
+    /// passing through [`plane.v0()`](HyperplaneBasis::v0), and directed by `plane`.
+    ///
+    /// See [`reflect_point_across`] for more info on when this is exact.
+    ///
+    /// Returns `None` if a reflected shape is rejected by it's constructor, (e.g. a simplex
+    /// whose vertices, after rounding, became affinely dependent).
+    #[must_use]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self>;
+}
+
+/// Returns the mirrors in `half`, followed by their images w.r.t. `plane`, in the
+/// same order.
+///
+/// Returns `None` if one of these images is invalid, see [`ReflectAcross::reflect_across`].
+///
+/// ```
+/// use miroir::{nalgebra::Vector2, HyperplaneBasis};
+/// use miroir_shapes::{mirrored, LineSegment};
+///
+/// // the y axis
+/// let (_, axis) = HyperplaneBasis::try_new([[0., 0.].into(), [0., 1.].into()]).unwrap();
+///
+/// let walls = mirrored(&[LineSegment::new([[0.634, -1.], [1.2, 1.]])], &axis).unwrap();
+///
+/// // the x coordinates are negated exactly
+/// let [a, b] = walls[0].vertices();
+/// assert_eq!(
+///     walls[1].vertices(),
+///     [Vector2::new(-a.x, a.y), Vector2::new(-b.x, b.y)],
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn mirrored<S, const D: usize, M>(
+    half: &[M],
+    plane: &HyperplaneBasisOrtho<S, D>,
+) -> Option<Vec<M>>
+where
+    S: ComplexField,
+    M: Clone + ReflectAcross<S, D>,
+{
+    half.iter()
+        .cloned()
+        .map(Some)
+        .chain(half.iter().map(|mirror| mirror.reflect_across(plane)))
+        .collect()
+}
+
+use impl_trait_for_tuples::impl_for_tuples;
+
+#[impl_for_tuples(1, 16)]
+impl<S: ComplexField, const D: usize> ReflectAcross<S, D> for T {
+    for_tuples!( where #( T: ReflectAcross<S, D> )* );
+
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        Some(for_tuples!( ( #( self.T.reflect_across(plane)? ),* ) ))
+    }
+}
+
+impl<const N: usize, S: ComplexField, const D: usize, T: ReflectAcross<S, D>> ReflectAcross<S, D>
+    for [T; N]
+{
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        let mirrors = self
+            .iter()
+            .map(|mirror| mirror.reflect_across(plane))
+            .collect::<Option<ArrayVec<_, N>>>()?;
+
+        match mirrors.into_inner() {
+            Ok(mirrors) => Some(mirrors),
+            Err(_) => unreachable!("the array has N elements"),
+        }
+    }
+}
+
+impl<S: ComplexField, const D: usize, T: ReflectAcross<S, D>> ReflectAcross<S, D>
+    for Prioritized<T>
+{
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        self.0
+            .reflect_across(plane)
+            .map(|mirror| Self(mirror, self.1))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: ReflectAcross<S, D>> ReflectAcross<S, D> for Box<T> {
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        (**self).reflect_across(plane).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: ReflectAcross<S, D>> ReflectAcross<S, D> for Arc<T> {
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        (**self).reflect_across(plane).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: ReflectAcross<S, D>> ReflectAcross<S, D> for Rc<T> {
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        (**self).reflect_across(plane).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: ReflectAcross<S, D>> ReflectAcross<S, D> for Vec<T> {
+    #[inline]
+    fn reflect_across(&self, plane: &HyperplaneBasisOrtho<S, D>) -> Option<Self> {
+        self.iter()
+            .map(|mirror| mirror.reflect_across(plane))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mirrored_scenes_have_symmetric_trajectories() {
+        // the y axis
+        let (_, axis) = HyperplaneBasis::try_new([[0., 0.].into(), [0., 1.].into()]).unwrap();
+
+        // the right half of a closed box, with an obstacle in it
+        let half = [
+            LineSegment::new([[0., -2.], [2., -2.]]),
+            LineSegment::new([[2., -2.], [2., 2.]]),
+            LineSegment::new([[2., 2.], [0., 2.]]),
+            LineSegment::new([[0.5, 0.5], [1.5, 1.2]]),
+        ];
+        let scene = mirrored(&half, &axis).unwrap();
+
+        let right = Ray::new([0.3, 0.1], [0.7, 0.45]);
+        let left = Ray::new([-0.3, 0.1], [-0.7, 0.45]);
+
+        let mut reflections = 0;
+
+        for (r, l) in RayPath::new(right, &scene, 1e-9)
+            .zip(RayPath::new(left, &scene, 1e-9))
+            .take(50)
+        {
+            // bitwise equal, up to the sign of the x coordinates
+            assert_eq!([r.origin.x, r.origin.y], [-l.origin.x, l.origin.y]);
+            assert_eq!([r.dir.x, r.dir.y], [-l.dir.x, l.dir.y]);
+            reflections += 1;
+        }
+
+        assert_eq!(reflections, 50);
+    }
+}
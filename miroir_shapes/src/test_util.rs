@@ -0,0 +1,38 @@
+//! Helpers shared by the tests of several shapes.
+
+// not every combination of features, and debug assertions, runs every test using them
+#![allow(dead_code)]
+
+use super::*;
+
+/// The smallest radius accepted for a shape at `magnitude` units away from the origin.
+pub(crate) fn min_radius_f32(magnitude: f32) -> f32 {
+    magnitude * f32::EPSILON * MIN_RELATIVE_RADIUS_ULPS as f32
+}
+
+/// splitmix64, for reproducibility
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A float in `[-1, 1)`
+    pub(crate) fn float(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 23) as f32 - 1.
+    }
+
+    /// A float in `[-1, 1)`
+    pub(crate) fn float_f64(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 52) as f64 - 1.
+    }
+}
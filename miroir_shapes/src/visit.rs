@@ -0,0 +1,318 @@
+use super::*;
+use core::any::Any;
+use nalgebra::ComplexField;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::ops::Deref;
+
+/// A set of callbacks, one per shape provided by this crate, through which
+/// [`VisitMirrors`] implementors expose their leaves (individual shapes).
+///
+/// `id` is the position of the visited leaf among all leaves of the mirror being visited,
+/// in visiting order. Visiting the same mirror always yields the same ids.
+///
+/// All callbacks do nothing by default.
+pub trait MirrorVisitor<S: ComplexField, const D: usize> {
+    #[inline]
+    fn visit_sphere(&mut self, _id: usize, _sphere: &Sphere<S, D>) {}
+
+    #[inline]
+    fn visit_simplex(&mut self, _id: usize, _simplex: &Simplex<S, D>) {}
+
+    /// Only ever called when `D == 3`.
+    #[inline]
+    fn visit_cylinder(&mut self, _id: usize, _cylinder: &Cylinder<S>) {}
+
+    /// Called for shapes not provided by this crate, (see [`visit_other_leaf`]), `type_name`
+    /// is the name of `mirror`'s type, as returned by [`core::any::type_name`].
+    #[inline]
+    fn visit_other(&mut self, _id: usize, _mirror: &dyn Any, _type_name: &'static str) {}
+}
+
+/// A trait enabling external tools to enumerate the leaves of a mirror, and access their
+/// geometric parameters, through a [`MirrorVisitor`].
+///
+/// Leaves call the callback of the visitor matching their type. Containers
+/// (tuples, arrays, slices, etc...) visit their elements, in order.
+///
+/// ```
+/// use miroir_shapes::{LineSegment, MirrorVisitor, Simplex, Sphere, VisitMirrors};
+///
+/// #[derive(Default)]
+/// struct Kinds(Vec<(usize, &'static str)>);
+///
+/// impl MirrorVisitor<f64, 2> for Kinds {
+///     fn visit_sphere(&mut self, id: usize, _sphere: &Sphere<f64, 2>) {
+///         self.0.push((id, "sphere"));
+///     }
+///
+///     fn visit_simplex(&mut self, id: usize, _simplex: &Simplex<f64, 2>) {
+///         self.0.push((id, "simplex"));
+///     }
+/// }
+///
+/// let mirrors = (
+///     Sphere::new([0., 0.], 1.),
+///     [
+///         LineSegment::new([[2., -1.], [2., 1.]]),
+///         LineSegment::new([[3., -1.], [3., 1.]]),
+///     ],
+/// );
+///
+/// let mut kinds = Kinds::default();
+/// mirrors.visit_leaves(&mut kinds);
+///
+/// assert_eq!(kinds.0, [(0, "sphere"), (1, "simplex"), (2, "simplex")]);
+/// ```
+pub trait VisitMirrors<S: ComplexField, const D: usize> {
+    /// Visits the leaves of `self`, in order, with `visitor`. The first leaf visited gets
+    /// `*next_id` as it's id, which is then incremented once for every leaf visited.
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize);
+
+    /// Visits the leaves of `self`, in order, with `visitor`, starting with the id `0`.
+    /// Returns the number of leaves visited.
+    #[inline]
+    fn visit_leaves(&self, visitor: &mut dyn MirrorVisitor<S, D>) -> usize {
+        let mut next_id = 0;
+        self.visit_mirrors(visitor, &mut next_id);
+        next_id
+    }
+}
+
+/// Returns `*next_id`, and increments it.
+#[inline]
+fn take_id(next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    id
+}
+
+/// Visits `mirror`, a shape not provided by this crate, with [`MirrorVisitor::visit_other`].
+///
+/// Meant for implementing [`VisitMirrors`] for such shapes:
+///
+/// ```
+/// use miroir::{Mirror, SimulationCtx};
+/// use miroir_shapes::{visit_other_leaf, MirrorVisitor, VisitMirrors};
+///
+/// struct Nothing;
+///
+/// impl Mirror<2> for Nothing {
+///     type Scalar = f64;
+///     fn add_tangents(&self, _ctx: &mut SimulationCtx<f64, 2>) {}
+/// }
+///
+/// impl VisitMirrors<f64, 2> for Nothing {
+///     fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<f64, 2>, next_id: &mut usize) {
+///         visit_other_leaf(self, visitor, next_id);
+///     }
+/// }
+/// ```
+#[inline]
+pub fn visit_other_leaf<S: ComplexField, const D: usize, M: Any>(
+    mirror: &M,
+    visitor: &mut dyn MirrorVisitor<S, D>,
+    next_id: &mut usize,
+) {
+    visitor.visit_other(take_id(next_id), mirror, core::any::type_name::<M>());
+}
+
+impl<S: ComplexField, const D: usize> VisitMirrors<S, D> for Sphere<S, D> {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        visitor.visit_sphere(take_id(next_id), self);
+    }
+}
+
+impl<S: ComplexField, const D: usize> VisitMirrors<S, D> for Simplex<S, D> {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        visitor.visit_simplex(take_id(next_id), self);
+    }
+}
+
+impl<S: ComplexField> VisitMirrors<S, 3> for Cylinder<S> {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, 3>, next_id: &mut usize) {
+        visitor.visit_cylinder(take_id(next_id), self);
+    }
+}
+
+use impl_trait_for_tuples::impl_for_tuples;
+
+#[impl_for_tuples(1, 16)]
+impl<S: ComplexField, const D: usize> VisitMirrors<S, D> for T {
+    for_tuples!( where #( T: VisitMirrors<S, D> )* );
+
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        for_tuples!( #( T.visit_mirrors(visitor, next_id); )* );
+    }
+}
+
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D>> VisitMirrors<S, D> for [T] {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.iter()
+            .for_each(|mirror| mirror.visit_mirrors(visitor, next_id));
+    }
+}
+
+impl<const N: usize, S: ComplexField, const D: usize, T: VisitMirrors<S, D>> VisitMirrors<S, D>
+    for [T; N]
+{
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.as_slice().visit_mirrors(visitor, next_id);
+    }
+}
+
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D>> VisitMirrors<S, D> for Prioritized<T> {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.0.visit_mirrors(visitor, next_id);
+    }
+}
+
+// It's clear that all these impls use the `Deref` trait, but writing a blanket impl over all
+// types implementing `Deref` makes it impossible to implement it for new types downstream.
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D> + ?Sized> VisitMirrors<S, D>
+    for Box<T>
+{
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.deref().visit_mirrors(visitor, next_id);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D> + ?Sized> VisitMirrors<S, D>
+    for Arc<T>
+{
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.deref().visit_mirrors(visitor, next_id);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D> + ?Sized> VisitMirrors<S, D> for Rc<T> {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.deref().visit_mirrors(visitor, next_id);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D>> VisitMirrors<S, D> for Vec<T> {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        self.as_slice().visit_mirrors(visitor, next_id);
+    }
+}
+
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D> + ?Sized> VisitMirrors<S, D> for &T {
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        (*self).visit_mirrors(visitor, next_id);
+    }
+}
+
+impl<S: ComplexField, const D: usize, T: VisitMirrors<S, D> + ?Sized> VisitMirrors<S, D>
+    for &mut T
+{
+    #[inline]
+    fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<S, D>, next_id: &mut usize) {
+        (**self).visit_mirrors(visitor, next_id);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    /// A shape not provided by this crate, at a single point.
+    struct Point([f64; 3]);
+
+    impl Mirror<3> for Point {
+        type Scalar = f64;
+        fn add_tangents(&self, _ctx: &mut SimulationCtx<f64, 3>) {}
+    }
+
+    impl VisitMirrors<f64, 3> for Point {
+        fn visit_mirrors(&self, visitor: &mut dyn MirrorVisitor<f64, 3>, next_id: &mut usize) {
+            visit_other_leaf(self, visitor, next_id);
+        }
+    }
+
+    /// Collects the id, kind, and a point of every visited leaf.
+    #[derive(Default)]
+    struct Leaves(Vec<(usize, &'static str, [f64; 3])>);
+
+    impl MirrorVisitor<f64, 3> for Leaves {
+        fn visit_sphere(&mut self, id: usize, sphere: &Sphere<f64, 3>) {
+            self.0.push((id, "sphere", sphere.center.into()));
+        }
+
+        fn visit_simplex(&mut self, id: usize, simplex: &Simplex<f64, 3>) {
+            self.0.push((id, "simplex", simplex.vertices()[0].into()));
+        }
+
+        fn visit_cylinder(&mut self, id: usize, cylinder: &Cylinder<f64>) {
+            self.0.push((id, "cylinder", (*cylinder.start()).into()));
+        }
+
+        fn visit_other(&mut self, id: usize, mirror: &dyn core::any::Any, type_name: &str) {
+            assert!(type_name.ends_with("Point"), "{type_name}");
+            let point = mirror.downcast_ref::<Point>().unwrap();
+            self.0.push((id, "other", point.0));
+        }
+    }
+
+    #[test]
+    fn nested_leaves_are_visited_once_in_order() {
+        let triangle = |z| Triangle::new([[0., 0., z], [1., 0., z], [0., 1., z]]);
+
+        let scene = (
+            Sphere::<f64, 3>::new([0., 0., 1.], 0.5),
+            alloc::vec![
+                (triangle(2.), Point([0., 0., 3.])),
+                (triangle(4.), Point([0., 0., 5.])),
+            ],
+            Prioritized(
+                (
+                    Box::new(Cylinder::new([0., 0., 6.], [0., 0., 6.5], 0.5)),
+                    [
+                        Sphere::new([0., 0., 7.], 0.5),
+                        Sphere::new([0., 0., 8.], 0.5),
+                    ],
+                ),
+                1,
+            ),
+        );
+
+        let mut leaves = Leaves::default();
+        assert_eq!(scene.visit_leaves(&mut leaves), 8);
+
+        let kinds = [
+            "sphere", "simplex", "other", "simplex", "other", "cylinder", "sphere", "sphere",
+        ];
+
+        assert_eq!(leaves.0.len(), kinds.len());
+
+        for (i, (&(id, kind, point), expected)) in leaves.0.iter().zip(kinds).enumerate() {
+            assert_eq!(id, i);
+            assert_eq!(kind, expected);
+            // every leaf was placed at a different height
+            assert_eq!(point[2], (i + 1) as f64);
+        }
+
+        // visiting again yields the same ids
+        let mut again = Leaves::default();
+        scene.visit_leaves(&mut again);
+        assert_eq!(leaves.0, again.0);
+    }
+}